@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use serenity::all::{Activity, ActivityType, Presence};
+
+/// The kinds of activity `%current_activity%` can be filled in with
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    Playing,
+    Streaming,
+    Competing,
+    Listening,
+    Watching,
+    /// A custom status set by the member
+    Custom,
+}
+
+impl ActivityKind {
+    pub(crate) const ALL: [ActivityKind; 6] = [
+        ActivityKind::Playing,
+        ActivityKind::Streaming,
+        ActivityKind::Competing,
+        ActivityKind::Listening,
+        ActivityKind::Watching,
+        ActivityKind::Custom,
+    ];
+
+    /// The value used for this kind in command options
+    pub(crate) fn id(&self) -> &'static str {
+        match self {
+            ActivityKind::Playing => "playing",
+            ActivityKind::Streaming => "streaming",
+            ActivityKind::Competing => "competing",
+            ActivityKind::Listening => "listening",
+            ActivityKind::Watching => "watching",
+            ActivityKind::Custom => "custom",
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ActivityKind::Playing => "Playing",
+            ActivityKind::Streaming => "Streaming",
+            ActivityKind::Competing => "Competing",
+            ActivityKind::Listening => "Listening",
+            ActivityKind::Watching => "Watching",
+            ActivityKind::Custom => "Custom status",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<ActivityKind> {
+        ActivityKind::ALL
+            .into_iter()
+            .find(|kind| kind.id() == id)
+    }
+
+    pub(crate) fn from_activity_type(activity_type: ActivityType) -> Option<ActivityKind> {
+        match activity_type {
+            ActivityType::Playing => Some(ActivityKind::Playing),
+            ActivityType::Streaming => Some(ActivityKind::Streaming),
+            ActivityType::Competing => Some(ActivityKind::Competing),
+            ActivityType::Listening => Some(ActivityKind::Listening),
+            ActivityType::Watching => Some(ActivityKind::Watching),
+            ActivityType::Custom => Some(ActivityKind::Custom),
+            _ => None,
+        }
+    }
+}
+
+/// Which of the member's activities is shown, kinds earlier in [`Self::order`] win over later ones
+/// so a game is picked over music that is playing at the same time
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ActivityPriority {
+    /// Kinds that are not in the list are never shown
+    pub(crate) order: Vec<ActivityKind>,
+    /// Names of activities that are skipped, like launchers such as `Steam`
+    pub(crate) ignored: Vec<String>,
+}
+
+impl Default for ActivityPriority {
+    /// Every kind but custom statuses, those are only shown when a server adds them to the order
+    fn default() -> Self {
+        Self {
+            order: vec![
+                ActivityKind::Playing,
+                ActivityKind::Streaming,
+                ActivityKind::Competing,
+                ActivityKind::Listening,
+                ActivityKind::Watching,
+            ],
+            ignored: Vec::new(),
+        }
+    }
+}
+
+impl ActivityPriority {
+    /// The activity of the presence with the highest priority that is not ignored
+    pub(crate) fn choose<'a>(&self, presence: &'a Presence) -> Option<&'a Activity> {
+        presence
+            .activities
+            .iter()
+            .filter(|activity| !self.is_ignored(activity))
+            .filter_map(|activity| {
+                let kind = ActivityKind::from_activity_type(activity.kind)?;
+                let priority = self.order.iter().position(|ordered| *ordered == kind)?;
+                Some((priority, activity))
+            })
+            .min_by_key(|(priority, _)| *priority)
+            .map(|(_, activity)| activity)
+    }
+
+    fn is_ignored(&self, activity: &Activity) -> bool {
+        match get_activity_name(activity) {
+            None => true,
+            Some(name) => self.ignored.iter().any(|ignored| ignored.eq_ignore_ascii_case(name.trim())),
+        }
+    }
+}
+
+/// What is shown for the activity, a custom status shows the text the member wrote
+pub(crate) fn get_activity_name(activity: &Activity) -> Option<&str> {
+    match activity.kind {
+        ActivityType::Custom => activity.state.as_deref().filter(|state| !state.trim().is_empty()),
+        _ => Some(activity.name.as_str()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::activity_priority::{get_activity_name, ActivityKind, ActivityPriority};
+    use serenity::all::Presence;
+
+    fn create_presence(activities: serde_json::Value) -> Presence {
+        serde_json::from_value(serde_json::json!({
+            "user": { "id": "1" },
+            "status": "online",
+            "activities": activities,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn check_activity_priority() {
+        let presence = create_presence(serde_json::json!([
+            { "name": "Custom Status", "type": 4, "created_at": 0, "state": "Grinding ranked" },
+            { "name": "Spotify", "type": 2, "created_at": 0 },
+            { "name": "Steam", "type": 0, "created_at": 0 },
+            { "name": "Valorant", "type": 0, "created_at": 0 },
+        ]));
+        let chosen = |priority: &ActivityPriority| priority.choose(&presence).and_then(get_activity_name);
+
+        let mut priority = ActivityPriority::default();
+        assert_eq!(chosen(&priority), Some("Steam"));
+
+        priority.ignored = vec!["steam".to_string(), "Battle.net".to_string()];
+        assert_eq!(chosen(&priority), Some("Valorant"));
+
+        priority.order = vec![ActivityKind::Listening, ActivityKind::Playing];
+        assert_eq!(chosen(&priority), Some("Spotify"));
+
+        priority.order = vec![ActivityKind::Custom];
+        assert_eq!(chosen(&priority), Some("Grinding ranked"));
+
+        priority.order = vec![ActivityKind::Watching];
+        assert_eq!(chosen(&priority), None);
+
+        let presence = create_presence(serde_json::json!([{ "name": "Custom Status", "type": 4, "created_at": 0 }]));
+        assert!(ActivityPriority::default().choose(&presence).is_none());
+    }
+
+    #[test]
+    fn check_custom_status_only() {
+        let presence = create_presence(serde_json::json!([
+            { "name": "Custom Status", "type": 4, "created_at": 0, "state": "Grinding ranked" },
+        ]));
+        assert!(ActivityPriority::default().choose(&presence).is_none());
+
+        let priority = ActivityPriority {
+            order: ActivityKind::ALL.to_vec(),
+            ignored: Vec::new(),
+        };
+        assert_eq!(priority.choose(&presence).and_then(get_activity_name), Some("Grinding ranked"));
+    }
+}
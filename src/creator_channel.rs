@@ -1,7 +1,17 @@
+use crate::permission_preset::PermissionPreset;
+use crate::position_strategy::PositionStrategy;
 use serde::{Deserialize, Serialize};
-use serenity::all::{ChannelId, GuildId};
+use serenity::all::{ChannelId, GuildId, RoleId, VideoQualityMode};
+use rand::{rng, Rng};
 use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 use std::num::{NonZero, NonZeroU16};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Highest number picked by [`NumberingStrategy::Random`] when the creator channel has no limit or range
+const RANDOM_NUMBER_MAX: u16 = 99;
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CreatorChannelConfig {
@@ -9,16 +19,300 @@ pub struct CreatorChannelConfig {
     pub(crate) creator_id: ChannelId,
     pub(crate) category_id: ChannelId,
     pub(crate) naming_standard: String,
-    pub(crate) channel_numbers: HashSet<NonZeroU16>,
+    #[serde(flatten)]
+    pub(crate) numbers: ChannelNumbers,
     pub(crate) user_limit: u32,
+    #[serde(default)]
+    pub(crate) channel_properties: ChannelPropertyOverrides,
+    #[serde(default)]
+    pub(crate) permission_preset: PermissionPreset,
+    /// Most temporary channels this creator channel can have at once
+    #[serde(default)]
+    pub(crate) max_channels: Option<u16>,
+    /// Sent to members who join while the creator channel is at [`Self::max_channels`]
+    #[serde(default)]
+    pub(crate) limit_message: Option<String>,
+    /// Most temporary channels a single member can own in the server when joining this creator channel
+    #[serde(default)]
+    pub(crate) max_owned_channels: Option<u16>,
+    /// When not empty only members with one of these roles get a channel
+    #[serde(default)]
+    pub(crate) allowed_role_ids: Vec<RoleId>,
+    /// Members with any of these roles never get a channel
+    #[serde(default)]
+    pub(crate) denied_role_ids: Vec<RoleId>,
+    #[serde(default)]
+    pub(crate) numbering_strategy: NumberingStrategy,
+    /// Numbers temporary channels can be given, without it numbers start from 1
+    #[serde(default)]
+    pub(crate) number_range: Option<NumberRange>,
+    /// Name of the [`crate::number_pool::NumberPool`] numbers are taken from instead of [`Self::numbers`]
+    #[serde(default)]
+    pub(crate) number_pool: Option<String>,
+    /// Categories used in order once [`Self::category_id`] is full
+    #[serde(default)]
+    pub(crate) overflow_category_ids: Vec<ChannelId>,
+    /// Whether a new overflow category is created when every category is full
+    #[serde(default)]
+    pub(crate) auto_overflow: bool,
+    /// Whether a private text channel for the owner and the members in the voice channel is created
+    #[serde(default)]
+    pub(crate) companion_text: bool,
+    /// Seconds an empty temporary channel is kept so members can rejoin it
+    #[serde(default)]
+    pub(crate) grace_period: Option<u16>,
+    #[serde(default)]
+    pub(crate) position_strategy: PositionStrategy,
+    /// Whether temporary channels are renamed when the activity of their owner changes
+    #[serde(default)]
+    pub(crate) live_rename: bool,
+    /// Whether a member always gets the same `%room%` from this creator channel
+    #[serde(default)]
+    pub(crate) stable_room: bool,
+}
+
+/// Numbers in use by temporary channels, kept by a creator channel or shared through a number pool
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ChannelNumbers {
+    pub(crate) channel_numbers: HashSet<NonZeroU16>,
+    /// The number given to the last created channel, used to keep counting up
+    #[serde(default)]
+    pub(crate) last_number: Option<NonZeroU16>,
+    /// The day since the unix epoch [`Self::last_number`] was given out on
+    #[serde(default)]
+    pub(crate) last_number_day: u64,
+}
+
+/// Numbers between `min` and `max` (inclusive) that temporary channels can be given
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberRange {
+    pub(crate) min: NonZeroU16,
+    pub(crate) max: NonZeroU16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberAllocationError {
+    /// Every number the strategy could pick is in use, or was already given out
+    RangeExhausted(NumberRange),
+}
+
+impl Display for NumberAllocationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumberAllocationError::RangeExhausted(range) => write!(
+                f,
+                "There are no channel numbers left between {} and {}, join an existing channel or try again later!",
+                range.min, range.max
+            ),
+        }
+    }
+}
+
+/// How the number of a new temporary channel is picked
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NumberingStrategy {
+    /// The lowest number that is not in use
+    #[default]
+    LowestGap,
+    /// One more than the last number, only counting from the start again after a reset
+    Monotonic,
+    /// A random number that is not in use
+    Random,
+    /// One more than the last number given out today, counting from the start again every day (UTC)
+    Daily,
+}
+
+impl NumberingStrategy {
+    pub(crate) const ALL: [NumberingStrategy; 4] = [
+        NumberingStrategy::LowestGap,
+        NumberingStrategy::Monotonic,
+        NumberingStrategy::Random,
+        NumberingStrategy::Daily,
+    ];
+
+    /// The value used for this strategy in command options
+    pub(crate) fn id(&self) -> &'static str {
+        match self {
+            NumberingStrategy::LowestGap => "lowest_gap",
+            NumberingStrategy::Monotonic => "monotonic",
+            NumberingStrategy::Random => "random",
+            NumberingStrategy::Daily => "daily",
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            NumberingStrategy::LowestGap => "Fill the lowest gap",
+            NumberingStrategy::Monotonic => "Keep counting up until reset",
+            NumberingStrategy::Random => "Random",
+            NumberingStrategy::Daily => "Count up from 1 every day",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<NumberingStrategy> {
+        NumberingStrategy::ALL
+            .into_iter()
+            .find(|strategy| strategy.id() == id)
+    }
+}
+
+/// Properties given to created temporary channels, when a property is not set the creator channel's
+/// own value is used instead
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ChannelPropertyOverrides {
+    pub(crate) bitrate: Option<u32>,
+    pub(crate) rtc_region: Option<String>,
+    pub(crate) video_quality_mode: Option<VideoQualityMode>,
+    pub(crate) nsfw: Option<bool>,
+    pub(crate) slowmode: Option<u16>,
+    pub(crate) status: Option<String>,
 }
 
 impl CreatorChannelConfig {
-    pub(crate) fn get_next_number(&self) -> NonZeroU16 {
-        get_next_number(self, NonZero::new(1).expect("This should never be 0"))
+    pub(crate) fn get_next_number(&self) -> Result<NonZeroU16, NumberAllocationError> {
+        self.get_next_number_on(get_current_day(), &mut rng())
+    }
+
+    /// Picks the next number from the numbers of this creator channel's pool instead of its own
+    pub(crate) fn get_next_number_from(&self, numbers: &ChannelNumbers) -> Result<NonZeroU16, NumberAllocationError> {
+        self.get_next_number_from_on(numbers, get_current_day(), &mut rng())
+    }
+
+    fn get_next_number_on(&self, day: u64, rng: &mut impl Rng) -> Result<NonZeroU16, NumberAllocationError> {
+        self.get_next_number_from_on(&self.numbers, day, rng)
+    }
+
+    fn get_next_number_from_on(
+        &self,
+        numbers: &ChannelNumbers,
+        day: u64,
+        rng: &mut impl Rng,
+    ) -> Result<NonZeroU16, NumberAllocationError> {
+        let range = self.get_effective_range();
+        let number = numbers.get_next_number_on(self.numbering_strategy, range, day, rng);
+
+        // Counting up starts from the lowest free number again once it reaches the channel limit
+        match self.max_channels {
+            Some(max_channels) if number.is_err() && max_channels == range.max.get() => {
+                numbers.get_next_number_on(NumberingStrategy::LowestGap, range, day, rng)
+            }
+            _ => number,
+        }
+    }
+
+    /// The configured range, capped at the channel limit so no number goes above it
+    fn get_effective_range(&self) -> NumberRange {
+        let range = match self.number_range {
+            Some(number_range) => number_range,
+            None => {
+                let max = match self.numbering_strategy {
+                    NumberingStrategy::Random => self.max_channels.unwrap_or(RANDOM_NUMBER_MAX),
+                    _ => u16::MAX,
+                };
+
+                NumberRange {
+                    min: NonZero::new(1).expect("This should never be 0"),
+                    max: NonZeroU16::new(max).unwrap_or(NonZero::new(1).expect("This should never be 0")),
+                }
+            }
+        };
+
+        match self.max_channels.and_then(NonZeroU16::new) {
+            Some(max_channels) if max_channels < range.max => NumberRange { min: range.min, max: max_channels },
+            _ => range,
+        }
+    }
+
+    /// Whether the creator channel has as many temporary channels as it is allowed. Numbers are
+    /// never above the limit, see [`Self::get_effective_range`]
+    pub(crate) fn is_full(&self, channel_count: usize) -> bool {
+        match self.max_channels {
+            None => false,
+            Some(max_channels) => channel_count >= max_channels as usize,
+        }
+    }
+
+    /// Members can own a single temporary channel unless [`Self::max_owned_channels`] allows more
+    pub(crate) fn get_max_owned_channels(&self) -> u16 {
+        self.max_owned_channels.unwrap_or(1)
+    }
+
+    pub(crate) fn get_limit_message(&self) -> String {
+        match &self.limit_message {
+            Some(limit_message) => limit_message.clone(),
+            None => format!(
+                "All {} channels of this creator channel are in use, join one of them or try again later!",
+                self.max_channels.unwrap_or_default()
+            ),
+        }
+    }
+
+    pub(crate) fn is_member_allowed(&self, member_role_ids: &[RoleId]) -> bool {
+        if member_role_ids.iter().any(|role_id| self.denied_role_ids.contains(role_id)) {
+            return false;
+        }
+
+        self.allowed_role_ids.is_empty()
+            || member_role_ids.iter().any(|role_id| self.allowed_role_ids.contains(role_id))
+    }
+
+    /// Every category temporary channels can be created in, in the order they are filled
+    pub(crate) fn get_category_ids(&self) -> Vec<ChannelId> {
+        let mut category_ids = vec![self.category_id];
+        category_ids.extend(self.overflow_category_ids.iter().filter(|category_id| **category_id != self.category_id));
+        category_ids
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.numbers.reset();
+    }
+}
+
+impl ChannelNumbers {
+    fn get_next_number_on(
+        &self,
+        strategy: NumberingStrategy,
+        range: NumberRange,
+        day: u64,
+        rng: &mut impl Rng,
+    ) -> Result<NonZeroU16, NumberAllocationError> {
+        let number = match strategy {
+            NumberingStrategy::LowestGap => get_lowest_free_number(&self.channel_numbers, range.min, range.max),
+            NumberingStrategy::Monotonic => match self.last_number {
+                None => get_lowest_free_number(&self.channel_numbers, range.min, range.max),
+                Some(last_number) => last_number
+                    .checked_add(1)
+                    .and_then(|next| get_lowest_free_number(&self.channel_numbers, next.max(range.min), range.max)),
+            },
+            NumberingStrategy::Random => {
+                let free_numbers: Vec<NonZeroU16> = (range.min.get()..=range.max.get())
+                    .filter_map(NonZeroU16::new)
+                    .filter(|number| !self.channel_numbers.contains(number))
+                    .collect();
+
+                match free_numbers.len() {
+                    0 => None,
+                    len => Some(free_numbers[rng.random_range(0..len)]),
+                }
+            }
+            NumberingStrategy::Daily => match self.last_number {
+                Some(last_number) if self.last_number_day == day => last_number
+                    .checked_add(1)
+                    .and_then(|next| get_lowest_free_number(&self.channel_numbers, next.max(range.min), range.max)),
+                _ => get_lowest_free_number(&self.channel_numbers, range.min, range.max),
+            },
+        };
+
+        number.ok_or(NumberAllocationError::RangeExhausted(range))
     }
 
     pub(crate) fn add_number(&mut self, number: NonZeroU16) -> bool {
+        self.add_number_on(number, get_current_day())
+    }
+
+    fn add_number_on(&mut self, number: NonZeroU16, day: u64) -> bool {
+        self.last_number = Some(number);
+        self.last_number_day = day;
         self.channel_numbers.insert(number)
     }
 
@@ -26,95 +320,382 @@ impl CreatorChannelConfig {
         self.channel_numbers.remove(number)
     }
 
-    pub(crate) fn get_highest_number(&self) -> Option<NonZeroU16> {
+    #[cfg(test)]
+    fn get_highest_number(&self) -> Option<NonZeroU16> {
         self.channel_numbers.iter().max().cloned()
     }
-    
+
     pub(crate) fn reset(&mut self) {
         self.channel_numbers = HashSet::new();
+        self.last_number = None;
     }
 }
 
-fn get_next_number(
-    creator_channel_config: &CreatorChannelConfig,
-    number: NonZeroU16,
-) -> NonZeroU16 {
-    if creator_channel_config.channel_numbers.contains(&number) {
-        get_next_number(
-            creator_channel_config,
-            number
-                .checked_add(1)
-                .expect("This is adding a positive number so should never == 0"),
-        )
-    } else {
-        return number;
-    }
+fn get_current_day() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / SECONDS_PER_DAY)
+        .unwrap_or_default()
+}
+
+/// The lowest number between `min` and `max` (inclusive) that is not in use
+fn get_lowest_free_number(
+    channel_numbers: &HashSet<NonZeroU16>,
+    min: NonZeroU16,
+    max: NonZeroU16,
+) -> Option<NonZeroU16> {
+    (min.get()..=max.get())
+        .filter_map(NonZeroU16::new)
+        .find(|number| !channel_numbers.contains(number))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::creator_channel::CreatorChannelConfig;
-    use std::collections::HashSet;
+    use crate::creator_channel::{ChannelNumbers, CreatorChannelConfig, NumberAllocationError, NumberRange, NumberingStrategy};
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::{rng, SeedableRng};
+    use serenity::all::RoleId;
     use std::num::NonZeroU16;
 
-    #[test]
-    fn check_channel_numbers() {
-        let mut creator_channel_config = CreatorChannelConfig {
+    fn create_config(numbering_strategy: NumberingStrategy, number_range: Option<NumberRange>) -> CreatorChannelConfig {
+        CreatorChannelConfig {
             guild_id: Default::default(),
             creator_id: Default::default(),
             category_id: Default::default(),
             naming_standard: "".to_string(),
-            channel_numbers: HashSet::new(),
+            numbers: Default::default(),
             user_limit: 0,
-        };
+            channel_properties: Default::default(),
+            permission_preset: Default::default(),
+            max_channels: None,
+            limit_message: None,
+            max_owned_channels: None,
+            allowed_role_ids: Vec::new(),
+            denied_role_ids: Vec::new(),
+            numbering_strategy,
+            number_range,
+            number_pool: None,
+            overflow_category_ids: Vec::new(),
+            auto_overflow: false,
+            companion_text: false,
+            grace_period: None,
+            position_strategy: Default::default(),
+            live_rename: false,
+            stable_room: false,
+        }
+    }
 
-        let number_1 = creator_channel_config.get_next_number();
+    #[test]
+    fn check_channel_numbers() {
+        let mut creator_channel_config = create_config(NumberingStrategy::LowestGap, None);
+
+        let number_1 = creator_channel_config.get_next_number().unwrap();
         assert_eq!(number_1, NonZeroU16::new(1).unwrap());
-        creator_channel_config.add_number(number_1);
+        creator_channel_config.numbers.add_number(number_1);
 
-        let highest = creator_channel_config.get_highest_number();
-        assert_eq!(highest.is_some(), true);
+        let highest = creator_channel_config.numbers.get_highest_number();
+        assert!(highest.is_some());
         assert_eq!(highest.unwrap(), NonZeroU16::new(1).unwrap());
 
-        let number_2 = creator_channel_config.get_next_number();
+        let number_2 = creator_channel_config.get_next_number().unwrap();
         assert_eq!(number_2, NonZeroU16::new(2).unwrap());
-        creator_channel_config.add_number(number_2);
+        creator_channel_config.numbers.add_number(number_2);
 
-        let highest = creator_channel_config.get_highest_number();
-        assert_eq!(highest.is_some(), true);
+        let highest = creator_channel_config.numbers.get_highest_number();
+        assert!(highest.is_some());
         assert_eq!(highest.unwrap(), NonZeroU16::new(2).unwrap());
 
-        let number_3 = creator_channel_config.get_next_number();
+        let number_3 = creator_channel_config.get_next_number().unwrap();
         assert_eq!(number_3, NonZeroU16::new(3).unwrap());
-        creator_channel_config.add_number(number_3);
+        creator_channel_config.numbers.add_number(number_3);
 
-        let highest = creator_channel_config.get_highest_number();
-        assert_eq!(highest.is_some(), true);
+        let highest = creator_channel_config.numbers.get_highest_number();
+        assert!(highest.is_some());
         assert_eq!(highest.unwrap(), NonZeroU16::new(3).unwrap());
 
-        creator_channel_config.remove_number(&number_2);
+        creator_channel_config.numbers.remove_number(&number_2);
 
-        let number_2 = creator_channel_config.get_next_number();
+        let number_2 = creator_channel_config.get_next_number().unwrap();
         assert_eq!(number_2, NonZeroU16::new(2).unwrap());
-        creator_channel_config.add_number(number_2);
+        creator_channel_config.numbers.add_number(number_2);
 
-        let highest = creator_channel_config.get_highest_number();
-        assert_eq!(highest.is_some(), true);
+        let highest = creator_channel_config.numbers.get_highest_number();
+        assert!(highest.is_some());
         assert_eq!(highest.unwrap(), NonZeroU16::new(3).unwrap());
 
-        creator_channel_config.remove_number(&number_2);
-        creator_channel_config.remove_number(&number_1);
+        creator_channel_config.numbers.remove_number(&number_2);
+        creator_channel_config.numbers.remove_number(&number_1);
 
-        let number_1 = creator_channel_config.get_next_number();
+        let number_1 = creator_channel_config.get_next_number().unwrap();
         assert_eq!(number_1, NonZeroU16::new(1).unwrap());
-        creator_channel_config.add_number(number_1);
+        creator_channel_config.numbers.add_number(number_1);
 
-        let number_2 = creator_channel_config.get_next_number();
+        let number_2 = creator_channel_config.get_next_number().unwrap();
         assert_eq!(number_2, NonZeroU16::new(2).unwrap());
-        creator_channel_config.add_number(number_2);
+        creator_channel_config.numbers.add_number(number_2);
 
-        let highest = creator_channel_config.get_highest_number();
-        assert_eq!(highest.is_some(), true);
+        let highest = creator_channel_config.numbers.get_highest_number();
+        assert!(highest.is_some());
         assert_eq!(highest.unwrap(), NonZeroU16::new(3).unwrap());
     }
+
+    #[test]
+    fn check_max_channels() {
+        let mut creator_channel_config = create_config(NumberingStrategy::LowestGap, None);
+        creator_channel_config.max_channels = Some(2);
+
+        let number_1 = creator_channel_config.get_next_number().unwrap();
+        creator_channel_config.numbers.add_number(number_1);
+        assert!(!creator_channel_config.is_full(creator_channel_config.numbers.channel_numbers.len()));
+
+        let number_2 = creator_channel_config.get_next_number().unwrap();
+        creator_channel_config.numbers.add_number(number_2);
+        assert!(creator_channel_config.is_full(creator_channel_config.numbers.channel_numbers.len()));
+
+        creator_channel_config.numbers.remove_number(&number_1);
+        assert!(!creator_channel_config.is_full(creator_channel_config.numbers.channel_numbers.len()));
+
+        let number_1 = creator_channel_config.get_next_number().unwrap();
+        assert_eq!(number_1, NonZeroU16::new(1).unwrap());
+
+        creator_channel_config.max_channels = None;
+        creator_channel_config.numbers.add_number(number_1);
+        assert!(!creator_channel_config.is_full(creator_channel_config.numbers.channel_numbers.len()));
+
+        assert_eq!(creator_channel_config.get_max_owned_channels(), 1);
+        creator_channel_config.max_owned_channels = Some(3);
+        assert_eq!(creator_channel_config.get_max_owned_channels(), 3);
+    }
+
+    #[test]
+    fn check_allowed_roles() {
+        let verified = RoleId::new(1);
+        let ranked = RoleId::new(2);
+        let muted = RoleId::new(3);
+
+        let mut creator_channel_config = create_config(NumberingStrategy::LowestGap, None);
+
+        assert!(creator_channel_config.is_member_allowed(&[]));
+        assert!(creator_channel_config.is_member_allowed(&[muted]));
+
+        creator_channel_config.denied_role_ids.push(muted);
+        assert!(creator_channel_config.is_member_allowed(&[]));
+        assert!(!creator_channel_config.is_member_allowed(&[verified, muted]));
+
+        creator_channel_config.allowed_role_ids.push(verified);
+        creator_channel_config.allowed_role_ids.push(ranked);
+        assert!(!creator_channel_config.is_member_allowed(&[]));
+        assert!(creator_channel_config.is_member_allowed(&[ranked]));
+        assert!(creator_channel_config.is_member_allowed(&[verified, ranked]));
+        assert!(!creator_channel_config.is_member_allowed(&[ranked, muted]));
+    }
+
+    #[test]
+    fn check_numbering_strategies() {
+        let mut creator_channel_config = create_config(NumberingStrategy::Monotonic, None);
+
+        let number = |number: u16| NonZeroU16::new(number).unwrap();
+
+        // Monotonic keeps counting up even when a lower number is free
+        creator_channel_config.numbers.add_number_on(creator_channel_config.get_next_number_on(0, &mut rng()).unwrap(), 0);
+        creator_channel_config.numbers.add_number_on(creator_channel_config.get_next_number_on(0, &mut rng()).unwrap(), 0);
+        creator_channel_config.numbers.remove_number(&number(1));
+        assert_eq!(creator_channel_config.get_next_number_on(0, &mut rng()).unwrap(), number(3));
+
+        creator_channel_config.reset();
+        assert_eq!(creator_channel_config.get_next_number_on(0, &mut rng()).unwrap(), number(1));
+
+        // Daily counts up during the day and starts from 1 the next day
+        creator_channel_config.numbering_strategy = NumberingStrategy::Daily;
+        creator_channel_config.numbers.add_number_on(creator_channel_config.get_next_number_on(5, &mut rng()).unwrap(), 5);
+        creator_channel_config.numbers.add_number_on(creator_channel_config.get_next_number_on(5, &mut rng()).unwrap(), 5);
+        creator_channel_config.numbers.remove_number(&number(1));
+        assert_eq!(creator_channel_config.get_next_number_on(5, &mut rng()).unwrap(), number(3));
+        assert_eq!(creator_channel_config.get_next_number_on(6, &mut rng()).unwrap(), number(1));
+
+        // Random only picks free numbers up to the channel limit
+        creator_channel_config.reset();
+        creator_channel_config.numbering_strategy = NumberingStrategy::Random;
+        creator_channel_config.max_channels = Some(5);
+        for _ in 0..5 {
+            let next_number = creator_channel_config.get_next_number_on(0, &mut rng()).unwrap();
+            assert!(next_number <= number(5));
+            assert!(creator_channel_config.numbers.add_number_on(next_number, 0));
+        }
+    }
+
+    #[test]
+    fn check_numbers_at_max_channels() {
+        let number = |number: u16| NonZeroU16::new(number).unwrap();
+
+        for range in [None, Some(NumberRange { min: number(2), max: number(10) })] {
+            for numbering_strategy in NumberingStrategy::ALL {
+                let mut creator_channel_config = create_config(numbering_strategy, range);
+                creator_channel_config.max_channels = Some(3);
+                let highest = number(3);
+
+                // Deleting and making channels again never goes past the channel limit
+                for round in 0..10 {
+                    while !creator_channel_config.is_full(creator_channel_config.numbers.channel_numbers.len()) {
+                        let next_number = match creator_channel_config.get_next_number_on(0, &mut rng()) {
+                            Err(_) => break,
+                            Ok(next_number) => next_number,
+                        };
+                        assert!(next_number <= highest, "{:?} gave {}", numbering_strategy, next_number);
+                        assert!(creator_channel_config.numbers.add_number_on(next_number, 0));
+                    }
+
+                    let mut numbers: Vec<NonZeroU16> = creator_channel_config.numbers.channel_numbers.iter().cloned().collect();
+                    numbers.sort();
+                    creator_channel_config.numbers.remove_number(&numbers[round % numbers.len()]);
+                }
+
+                // Numbers from a pool stay at or below the limit even when other creator channels use it
+                let mut pool = ChannelNumbers::default();
+                pool.add_number(number(2));
+                pool.add_number(number(4));
+                for _ in 0..2 {
+                    if let Ok(next_number) = creator_channel_config.get_next_number_from(&pool) {
+                        assert!(next_number <= highest, "{:?} gave {} from a pool", numbering_strategy, next_number);
+                        pool.add_number(next_number);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn check_number_range_exhausted() {
+        let number = |number: u16| NonZeroU16::new(number).unwrap();
+        let range = NumberRange { min: number(3), max: number(4) };
+
+        let mut creator_channel_config = create_config(NumberingStrategy::LowestGap, Some(range));
+
+        assert_eq!(creator_channel_config.get_next_number_on(0, &mut rng()), Ok(number(3)));
+        creator_channel_config.numbers.add_number_on(number(3), 0);
+        assert_eq!(creator_channel_config.get_next_number_on(0, &mut rng()), Ok(number(4)));
+        creator_channel_config.numbers.add_number_on(number(4), 0);
+        assert_eq!(
+            creator_channel_config.get_next_number_on(0, &mut rng()),
+            Err(NumberAllocationError::RangeExhausted(range))
+        );
+
+        // Counting up past the highest possible number is an error instead of a panic
+        let mut creator_channel_config = create_config(NumberingStrategy::Monotonic, None);
+        creator_channel_config.numbers.add_number_on(NonZeroU16::MAX, 0);
+        assert!(creator_channel_config.get_next_number_on(0, &mut rng()).is_err());
+
+        // Every possible number in use is an error instead of deep recursion
+        let mut creator_channel_config = create_config(NumberingStrategy::LowestGap, None);
+        creator_channel_config.numbers.channel_numbers = (1..=u16::MAX).filter_map(NonZeroU16::new).collect();
+        assert!(creator_channel_config.get_next_number_on(0, &mut rng()).is_err());
+    }
+
+    #[test]
+    fn check_shared_numbers() {
+        let number = |number: u16| NonZeroU16::new(number).unwrap();
+
+        let eu_config = create_config(NumberingStrategy::LowestGap, None);
+        let na_config = create_config(NumberingStrategy::LowestGap, None);
+        let mut pool = ChannelNumbers::default();
+
+        let eu_number = eu_config.get_next_number_from(&pool).unwrap();
+        pool.add_number(eu_number);
+        let na_number = na_config.get_next_number_from(&pool).unwrap();
+        pool.add_number(na_number);
+        assert_eq!((eu_number, na_number), (number(1), number(2)));
+
+        // Freeing a number in the pool makes it available to every creator channel sharing it
+        pool.remove_number(&eu_number);
+        assert_eq!(na_config.get_next_number_from(&pool), Ok(number(1)));
+
+        // Configs saved before numbers were split out still load
+        let config: CreatorChannelConfig = serde_json::from_str(
+            r#"{"guild_id":"1","creator_id":"2","category_id":"3","naming_standard":"%number%","channel_numbers":[4],"user_limit":0,"last_number":4}"#,
+        ).unwrap();
+        assert!(config.numbers.channel_numbers.contains(&number(4)));
+        assert_eq!(config.numbers.last_number, Some(number(4)));
+    }
+
+    #[derive(Debug, Clone)]
+    enum Operation {
+        Allocate,
+        Remove(usize),
+        NextDay,
+    }
+
+    fn operation() -> impl Strategy<Value = Operation> {
+        prop_oneof![
+            4 => Just(Operation::Allocate),
+            2 => any::<usize>().prop_map(Operation::Remove),
+            1 => Just(Operation::NextDay),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn check_allocation_sequences(
+            strategy in prop::sample::select(NumberingStrategy::ALL.to_vec()),
+            min in 1u16..20,
+            len in 0u16..20,
+            operations in prop::collection::vec(operation(), 0..200),
+            seed in any::<u64>(),
+        ) {
+            let range = NumberRange {
+                min: NonZeroU16::new(min).unwrap(),
+                max: NonZeroU16::new(min + len).unwrap(),
+            };
+            let mut creator_channel_config = create_config(strategy, Some(range));
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut day = 0;
+
+            for operation in operations {
+                match operation {
+                    Operation::Allocate => {
+                        let free_in_range = (range.min.get()..=range.max.get())
+                            .filter_map(NonZeroU16::new)
+                            .any(|number| !creator_channel_config.numbers.channel_numbers.contains(&number));
+
+                        let counts_up = match strategy {
+                            NumberingStrategy::Monotonic => creator_channel_config.numbers.last_number,
+                            NumberingStrategy::Daily if creator_channel_config.numbers.last_number_day == day => creator_channel_config.numbers.last_number,
+                            _ => None,
+                        };
+
+                        match creator_channel_config.get_next_number_on(day, &mut rng) {
+                            Ok(number) => {
+                                prop_assert!(range.min <= number && number <= range.max);
+                                prop_assert!(!creator_channel_config.numbers.channel_numbers.contains(&number));
+                                if let Some(last_number) = counts_up {
+                                    prop_assert!(number > last_number);
+                                }
+                                prop_assert!(creator_channel_config.numbers.add_number_on(number, day));
+                            }
+                            Err(NumberAllocationError::RangeExhausted(exhausted_range)) => {
+                                prop_assert_eq!(exhausted_range, range);
+                                match counts_up {
+                                    None => prop_assert!(!free_in_range),
+                                    Some(last_number) => prop_assert!(
+                                        (last_number.get().saturating_add(1)..=range.max.get())
+                                            .filter_map(NonZeroU16::new)
+                                            .all(|number| number < range.min || creator_channel_config.numbers.channel_numbers.contains(&number))
+                                    ),
+                                }
+                            }
+                        }
+                    }
+                    Operation::Remove(index) => {
+                        let mut numbers: Vec<NonZeroU16> = creator_channel_config.numbers.channel_numbers.iter().cloned().collect();
+                        numbers.sort();
+                        if !numbers.is_empty() {
+                            let number = numbers[index % numbers.len()];
+                            prop_assert!(creator_channel_config.numbers.remove_number(&number));
+                        }
+                    }
+                    Operation::NextDay => day += 1,
+                }
+            }
+        }
+    }
 }
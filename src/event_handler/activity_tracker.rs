@@ -0,0 +1,72 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use serenity::all::{GuildId, Presence, UserId};
+
+/// Remembers the activities of members, so presence updates that only change their online status
+/// do not rename their channels
+#[derive(Clone)]
+pub struct ActivityTracker {
+    activities: Arc<Mutex<HashMap<(GuildId, UserId), u64>>>,
+}
+
+impl ActivityTracker {
+    pub fn new() -> Self {
+        ActivityTracker {
+            activities: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Whether the activities differ from the ones in the last presence update of the member
+    pub fn activities_changed(&self, guild_id: GuildId, presence: &Presence) -> bool {
+        let activities_hash = get_activities_hash(presence);
+
+        let mut activities_lock = self.activities.lock().unwrap();
+        let previous_hash = activities_lock.insert((guild_id, presence.user.id), activities_hash);
+
+        previous_hash != Some(activities_hash)
+    }
+}
+
+/// Hashes what a naming standard can show of the activities
+fn get_activities_hash(presence: &Presence) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    for activity in &presence.activities {
+        u8::from(activity.kind).hash(&mut hasher);
+        activity.name.hash(&mut hasher);
+        activity.state.hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event_handler::activity_tracker::ActivityTracker;
+    use serenity::all::{GuildId, Presence};
+
+    fn create_presence(status: &str, activities: serde_json::Value) -> Presence {
+        serde_json::from_value(serde_json::json!({
+            "user": { "id": "1" },
+            "status": status,
+            "activities": activities,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn check_activities_changed() {
+        let tracker = ActivityTracker::new();
+        let guild_id = GuildId::new(1);
+        let valorant = serde_json::json!([{ "name": "Valorant", "type": 0, "created_at": 0 }]);
+
+        assert!(tracker.activities_changed(guild_id, &create_presence("online", valorant.clone())));
+        assert!(!tracker.activities_changed(guild_id, &create_presence("idle", valorant.clone())));
+        assert!(tracker.activities_changed(guild_id, &create_presence("idle", serde_json::json!([]))));
+        assert!(tracker.activities_changed(guild_id, &create_presence("idle", valorant.clone())));
+        assert!(tracker.activities_changed(GuildId::new(2), &create_presence("idle", valorant)));
+    }
+}
@@ -1,35 +1,45 @@
 use std::collections::HashMap;
 
-use serenity::all::{ChannelId, CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::all::{ChannelId, ChannelType, CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateInteractionResponse, CreateInteractionResponseMessage};
 use serenity::builder::CreateCommandOption;
 
 use crate::creator_channel::CreatorChannelConfig;
+use crate::event_handler::commands::creator_channel::permission_preset_option;
+use crate::event_handler::commands::creator_channel::preview::render_examples;
+use crate::event_handler::commands::creator_channel::validation::{validate_channel_kind, validate_naming_standard, validate_permission_preset, validate_user_limit, MAX_CHANNEL_NAME_LENGTH, MAX_USER_LIMIT};
 use crate::StorageKey;
 
 pub fn get_command_option() -> CreateCommandOption {
     CreateCommandOption::new(CommandOptionType::SubCommand, "add", "Adds a creator channel")
         .add_sub_option(
             CreateCommandOption::new(CommandOptionType::Channel, "creator_id", "Channel to be the creator channel")
+                .channel_types(vec![ChannelType::Voice])
                 .required(true),
         )
         .add_sub_option(
             CreateCommandOption::new(CommandOptionType::Channel, "category_id", "Category for the temporary channel to be created in")
+                .channel_types(vec![ChannelType::Category])
                 .required(true),
         )
         .add_sub_option(
             CreateCommandOption::new(CommandOptionType::String, "naming_standard", "Naming standard")
+                .max_length(MAX_CHANNEL_NAME_LENGTH as u16)
+                .set_autocomplete(true)
                 .required(true),
         )
         .add_sub_option(
             CreateCommandOption::new(CommandOptionType::Integer, "user_limit", "User limit")
+                .min_int_value(0)
+                .max_int_value(MAX_USER_LIMIT as u64)
                 .required(true),
         )
+        .add_sub_option(permission_preset_option())
 }
 
 pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteractionResponse {
-    let creator_channel_config = match get_creator_channel_config(command) {
-        None => return create_response("Something went wrong when trying to parse the command options!"),
-        Some(creator_channel_config) => creator_channel_config,
+    let creator_channel_config = match get_creator_channel_config(ctx, command).await {
+        Err(why) => return create_ephemeral_response(&why),
+        Ok(creator_channel_config) => creator_channel_config,
     };
 
     let storage = {
@@ -45,30 +55,36 @@ pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteracti
 
     storage.set_creator_voice_config(&creator_channel_config).await;
 
-    return create_response("Added creator channel to the database!");
+    create_response(&format!(
+        "Added creator channel to the database! Channels will be named like:\n{}",
+        render_examples(ctx, command, creator_channel_config.guild_id, Some(creator_channel_config.creator_id), &creator_channel_config.naming_standard).await
+    ))
 }
 
 macro_rules! extract_option {
     ($map:expr, $key:expr, $method:ident) => {
-        $map.get($key)?.$method()
+        match $map.get($key).and_then(|value| value.$method()) {
+            None => return Err(format!("`{}` is missing or has the wrong type!", $key)),
+            Some(value) => value,
+        }
     };
 }
 
-fn get_creator_channel_config(command: &CommandInteraction) -> Option<CreatorChannelConfig> {
+async fn get_creator_channel_config(ctx: &Context, command: &CommandInteraction) -> Result<CreatorChannelConfig, String> {
     let guild_id = match command.guild_id {
-        None => return None,
-        Some(guild_id) =>guild_id,
+        None => return Err("This command can only be used in a server.".to_string()),
+        Some(guild_id) => guild_id,
     };
 
-    let reset_option = match command.data.options.iter().find(|opt| opt.name == "add") {
-        None => return None,
+    let add_option = match command.data.options.iter().find(|opt| opt.name == "add") {
+        None => return Err("Something went wrong when trying to parse the command options!".to_string()),
         Some(command_data_option) => command_data_option,
     };
 
-    let options = match &reset_option.value {
+    let options = match &add_option.value {
         CommandDataOptionValue::SubCommand(options) => options,
         CommandDataOptionValue::SubCommandGroup(options) => options,
-        _ => return None,
+        _ => return Err("Invalid subcommand or subcommand group format!".to_string()),
     };
 
     let option_map: HashMap<&str, &CommandDataOptionValue> = HashMap::from_iter(
@@ -77,19 +93,46 @@ fn get_creator_channel_config(command: &CommandInteraction) -> Option<CreatorCha
             .map(|opt| (opt.name.as_str(), &opt.value))
     );
 
-    let creator_id:  ChannelId  = extract_option!(option_map, "creator_id",      as_channel_id)?;
-    let category_id: ChannelId  = extract_option!(option_map, "category_id",     as_channel_id)?;
-    let naming_standard: String = extract_option!(option_map, "naming_standard", as_str)?.to_string();
-    let user_limit: u32         = extract_option!(option_map, "user_limit",      as_i64)? as u32;
+    let creator_id:  ChannelId  = extract_option!(option_map, "creator_id",      as_channel_id);
+    let category_id: ChannelId  = extract_option!(option_map, "category_id",     as_channel_id);
+    let naming_standard: String = extract_option!(option_map, "naming_standard", as_str).to_string();
+    let user_limit: i64         = extract_option!(option_map, "user_limit",      as_i64);
+
+    validate_channel_kind(ctx, guild_id, creator_id, ChannelType::Voice, "creator_id").await?;
+    validate_channel_kind(ctx, guild_id, category_id, ChannelType::Category, "category_id").await?;
+    let user_limit = validate_user_limit(user_limit)?;
+    validate_naming_standard(ctx, command, guild_id, Some(creator_id), &naming_standard).await?;
+
+    let permission_preset = match option_map.get("permission_preset").and_then(|value| value.as_str()) {
+        None => Default::default(),
+        Some(permission_preset) => validate_permission_preset(permission_preset)?,
+    };
 
-    return Some(
+    Ok(
         CreatorChannelConfig{
             guild_id,
             creator_id,
             category_id,
             naming_standard,
-            channel_numbers: Default::default(),
+            numbers: Default::default(),
             user_limit,
+            channel_properties: Default::default(),
+            permission_preset,
+            max_channels: None,
+            limit_message: None,
+            max_owned_channels: None,
+            allowed_role_ids: Vec::new(),
+            denied_role_ids: Vec::new(),
+            numbering_strategy: Default::default(),
+            number_range: None,
+            number_pool: None,
+            overflow_category_ids: Vec::new(),
+            auto_overflow: false,
+            companion_text: false,
+            grace_period: None,
+            position_strategy: Default::default(),
+            live_rename: false,
+            stable_room: false,
         }
     )
 }
@@ -99,4 +142,12 @@ fn create_response(string: &str) -> CreateInteractionResponse {
         CreateInteractionResponseMessage::new()
             .content(string)
     )
-}
\ No newline at end of file
+}
+
+fn create_ephemeral_response(string: &str) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(string)
+    )
+}
@@ -0,0 +1,219 @@
+use serenity::all::{ChannelId, CommandInteraction, Context, CreateAutocompleteResponse, GuildId};
+
+use crate::template::{find_unclosed_placeholder, Placeholder};
+use crate::StorageKey;
+
+/// Discord shows at most this many autocomplete choices
+const MAX_CHOICES: usize = 25;
+
+/// Discord does not allow autocomplete choice names or values longer than this
+const MAX_CHOICE_LENGTH: usize = 100;
+
+pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateAutocompleteResponse {
+    let guild_id = match command.guild_id {
+        None => return CreateAutocompleteResponse::new(),
+        Some(guild_id) => guild_id,
+    };
+
+    let focused_option = match command.data.autocomplete() {
+        None => return CreateAutocompleteResponse::new(),
+        Some(focused_option) => focused_option,
+    };
+
+    match focused_option.name {
+        "creator_id" => complete_creator_channel(ctx, guild_id, focused_option.value).await,
+        "naming_standard" => complete_naming_standard(focused_option.value),
+        "rtc_region" => complete_rtc_region(ctx, focused_option.value).await,
+        "number_pool" => complete_number_pool(ctx, guild_id, focused_option.value).await,
+        _ => CreateAutocompleteResponse::new(),
+    }
+}
+
+/// Parses the value of a creator channel option, which is either the channel id picked from
+/// autocomplete or a channel mention typed out by hand
+pub(super) fn parse_channel_id(value: &str) -> Option<ChannelId> {
+    let value = value.trim();
+    let id = value
+        .strip_prefix("<#")
+        .and_then(|value| value.strip_suffix('>'))
+        .unwrap_or(value);
+
+    match id.parse::<u64>() {
+        Ok(0) | Err(_) => None,
+        Ok(id) => Some(ChannelId::new(id)),
+    }
+}
+
+async fn complete_creator_channel(ctx: &Context, guild_id: GuildId, value: &str) -> CreateAutocompleteResponse {
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    let configs = storage.get_creator_voice_configs(&guild_id).await.unwrap_or_default();
+
+    let channel_names: Vec<(ChannelId, String)> = {
+        let guild = guild_id.to_guild_cached(ctx);
+
+        configs
+            .iter()
+            .map(|config| {
+                let channel_name = guild
+                    .as_ref()
+                    .and_then(|guild| guild.channels.get(&config.creator_id))
+                    .map(|channel| channel.name.clone())
+                    .unwrap_or_else(|| config.creator_id.to_string());
+
+                (config.creator_id, format!("#{} ({})", channel_name, config.naming_standard))
+            })
+            .collect()
+    };
+
+    let search = value.trim().to_lowercase();
+
+    channel_names
+        .into_iter()
+        .filter(|(channel_id, name)| {
+            search.is_empty() || name.to_lowercase().contains(&search) || channel_id.to_string().contains(&search)
+        })
+        .take(MAX_CHOICES)
+        .fold(CreateAutocompleteResponse::new(), |response, (channel_id, name)| {
+            response.add_string_choice(truncate_choice(&name), channel_id.to_string())
+        })
+}
+
+/// Suggests the naming standard typed so far with each placeholder appended, or completes the
+/// placeholder that is currently being typed
+fn complete_naming_standard(value: &str) -> CreateAutocompleteResponse {
+    get_naming_standard_suggestions(value)
+        .into_iter()
+        .fold(CreateAutocompleteResponse::new(), |response, suggestion| {
+            response.add_string_choice(suggestion.clone(), suggestion)
+        })
+}
+
+fn get_naming_standard_suggestions(value: &str) -> Vec<String> {
+    let partial_start = find_unclosed_placeholder(value);
+
+    let (prefix, partial_placeholder) = match partial_start {
+        Some(index) => (&value[..index], &value[index..]),
+        None => (value, ""),
+    };
+
+    let mut suggestions: Vec<String> = Vec::new();
+
+    if !value.is_empty() && partial_start.is_none() {
+        suggestions.push(value.to_string());
+    }
+
+    suggestions.extend(
+        Placeholder::ALL
+            .iter()
+            .map(|placeholder| placeholder.token())
+            .filter(|token| token.starts_with(partial_placeholder))
+            .map(|token| format!("{}{}", prefix, token)),
+    );
+
+    suggestions
+        .into_iter()
+        .filter(|suggestion| suggestion.chars().count() <= MAX_CHOICE_LENGTH)
+        .take(MAX_CHOICES)
+        .collect()
+}
+
+async fn complete_rtc_region(ctx: &Context, value: &str) -> CreateAutocompleteResponse {
+    let regions = ctx.http.get_voice_regions().await.unwrap_or_default();
+    let search = value.trim().to_lowercase();
+
+    regions
+        .into_iter()
+        .filter(|region| !region.deprecated)
+        .filter(|region| {
+            search.is_empty() || region.id.contains(&search) || region.name.to_lowercase().contains(&search)
+        })
+        .take(MAX_CHOICES)
+        .fold(CreateAutocompleteResponse::new(), |response, region| {
+            response.add_string_choice(region.name, region.id)
+        })
+}
+
+/// Suggests the number pools other creator channels of the server already use
+async fn complete_number_pool(ctx: &Context, guild_id: GuildId, value: &str) -> CreateAutocompleteResponse {
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    let configs = storage.get_creator_voice_configs(&guild_id).await.unwrap_or_default();
+
+    let mut pool_names: Vec<String> = configs
+        .into_iter()
+        .filter_map(|config| config.number_pool)
+        .collect();
+    pool_names.sort();
+    pool_names.dedup();
+
+    let value = value.trim();
+    if !value.is_empty() && !pool_names.iter().any(|pool_name| pool_name == value) {
+        pool_names.insert(0, value.to_string());
+    }
+
+    let search = value.to_lowercase();
+
+    pool_names
+        .into_iter()
+        .filter(|pool_name| pool_name.to_lowercase().contains(&search))
+        .take(MAX_CHOICES)
+        .fold(CreateAutocompleteResponse::new(), |response, pool_name| {
+            response.add_string_choice(truncate_choice(&pool_name), pool_name)
+        })
+}
+
+fn truncate_choice(name: &str) -> String {
+    if name.chars().count() <= MAX_CHOICE_LENGTH {
+        return name.to_string();
+    }
+
+    let mut truncated: String = name.chars().take(MAX_CHOICE_LENGTH - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event_handler::commands::creator_channel::autocomplete::get_naming_standard_suggestions;
+
+    #[test]
+    fn check_naming_standard_suggestions() {
+        assert_eq!(
+            get_naming_standard_suggestions("Room ")[..5],
+            ["Room ", "Room %number%", "Room %name%", "Room %room%", "Room %current_activity%"]
+        );
+        assert_eq!(get_naming_standard_suggestions("%name% #%nu"), vec!["%name% #%number%"]);
+        assert_eq!(get_naming_standard_suggestions("100% %na"), vec!["100% %name%"]);
+        assert_eq!(get_naming_standard_suggestions("50\\% %nu"), vec!["50\\% %number%"]);
+        assert_eq!(
+            get_naming_standard_suggestions("100% done")[..5],
+            [
+                "100% done",
+                "100% done%number%",
+                "100% done%name%",
+                "100% done%room%",
+                "100% done%current_activity%",
+            ]
+        );
+        assert!(get_naming_standard_suggestions(&"a".repeat(100)).len() == 1);
+    }
+}
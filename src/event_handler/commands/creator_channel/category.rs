@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use serenity::all::{ChannelId, ChannelType, CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateInteractionResponse, CreateInteractionResponseMessage, Mentionable};
+use serenity::builder::CreateCommandOption;
+
+use crate::event_handler::commands::creator_channel::autocomplete::parse_channel_id;
+use crate::event_handler::commands::creator_channel::validation::validate_channel_kind;
+use crate::StorageKey;
+
+pub fn get_command_option() -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::SubCommand, "category", "Changes the categories used once a category is full")
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "creator_id", "Creator channel to change")
+                .set_autocomplete(true)
+                .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Channel, "overflow_category", "Category to use after the ones already listed")
+                .channel_types(vec![ChannelType::Category]),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Boolean, "remove", "Remove the category from the list instead"),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Boolean, "auto_overflow", "Create a new category when every category is full"),
+        )
+}
+
+pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteractionResponse {
+    let category_option = match command.data.options.iter().find(|opt| opt.name == "category") {
+        None => return create_response("Something went wrong when trying to parse the command options!"),
+        Some(command_data_option) => command_data_option,
+    };
+
+    let sub_options = match &category_option.value {
+        CommandDataOptionValue::SubCommand(options) => options,
+        CommandDataOptionValue::SubCommandGroup(options) => options,
+        _ => return create_response("Invalid subcommand or subcommand group format!"),
+    };
+
+    let option_map: HashMap<&str, &CommandDataOptionValue> = HashMap::from_iter(
+        sub_options.iter().map(|opt| (opt.name.as_str(), &opt.value))
+    );
+
+    let creator_id = match option_map.get("creator_id").and_then(|value| value.as_str()).and_then(parse_channel_id) {
+        None => return create_response("Pick a creator channel from the suggestions!"),
+        Some(channel_id) => channel_id,
+    };
+
+    let remove = option_map
+        .get("remove")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    let mut creator_channel = match storage.get_creator_voice_config(&creator_id).await {
+        Some(creator_channel) if Some(creator_channel.guild_id) == command.guild_id => creator_channel,
+        _ => return create_response("That channel is not a creator channel!"),
+    };
+
+    if let Some(category_id) = option_map.get("overflow_category").and_then(|value| value.as_channel_id()) {
+        if remove {
+            creator_channel.overflow_category_ids.retain(|listed_category_id| *listed_category_id != category_id);
+        } else {
+            if let Err(why) = validate_channel_kind(ctx, creator_channel.guild_id, category_id, ChannelType::Category, "overflow_category").await {
+                return create_response(&why);
+            }
+
+            if category_id == creator_channel.category_id {
+                return create_response("That category is already the first category of the creator channel!");
+            }
+
+            if !creator_channel.overflow_category_ids.contains(&category_id) {
+                creator_channel.overflow_category_ids.push(category_id);
+            }
+        }
+    }
+
+    if let Some(auto_overflow) = option_map.get("auto_overflow").and_then(|value| value.as_bool()) {
+        creator_channel.auto_overflow = auto_overflow;
+    }
+
+    storage.set_creator_voice_config(&creator_channel).await;
+
+    create_response(&format!(
+        "Updated the creator channel!\nCategories: {}\nCreate new categories when full: {}",
+        format_categories(&creator_channel.get_category_ids()),
+        if creator_channel.auto_overflow { "Yes" } else { "No" },
+    ))
+}
+
+fn format_categories(category_ids: &[ChannelId]) -> String {
+    category_ids
+        .iter()
+        .map(|category_id| category_id.mention().to_string())
+        .collect::<Vec<String>>()
+        .join(" → ")
+}
+
+fn create_response(string: &str) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(string)
+    )
+}
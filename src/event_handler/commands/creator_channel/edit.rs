@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use serenity::all::{ChannelType, CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateInteractionResponse, CreateInteractionResponseMessage, VideoQualityMode};
+use serenity::builder::CreateCommandOption;
+
+use crate::creator_channel::{CreatorChannelConfig, NumberingStrategy};
+use crate::position_strategy::PositionStrategy;
+use crate::event_handler::commands::creator_channel::autocomplete::parse_channel_id;
+use crate::event_handler::commands::creator_channel::permission_preset_option;
+use crate::event_handler::commands::creator_channel::preview::render_examples;
+use crate::event_handler::commands::creator_channel::validation::{validate_bitrate, validate_channel_kind, validate_grace_period, validate_max_channels, validate_max_owned_channels, validate_naming_standard, validate_number_pool, validate_number_range, validate_number_range_limit, validate_numbering_strategy, validate_permission_preset, validate_position_strategy, validate_rtc_region, validate_slowmode, validate_user_limit, MAX_BITRATE, MAX_CHANNEL_NAME_LENGTH, MAX_GRACE_PERIOD, MAX_GUILD_CHANNELS, MAX_LIMIT_MESSAGE_LENGTH, MAX_NUMBER_POOL_NAME_LENGTH, MAX_SLOWMODE, MAX_STATUS_LENGTH, MAX_USER_LIMIT, MIN_BITRATE};
+use crate::StorageKey;
+
+/// Settings that can be cleared back to their default, for channel properties that is the creator
+/// channel's own value
+const CLEARABLE_SETTINGS: [&str; 8] = ["bitrate", "rtc_region", "video_quality", "nsfw", "slowmode", "status", "number_range", "number_pool"];
+
+pub fn get_command_option() -> CreateCommandOption {
+    let numbering_strategy_option = NumberingStrategy::ALL.iter().fold(
+        CreateCommandOption::new(CommandOptionType::String, "numbering_strategy", "How the number of a new channel is picked"),
+        |option, strategy| option.add_string_choice(strategy.name(), strategy.id()),
+    );
+
+    let position_strategy_option = PositionStrategy::ALL.iter().fold(
+        CreateCommandOption::new(CommandOptionType::String, "position_strategy", "Where the creator channel and its channels are put"),
+        |option, strategy| option.add_string_choice(strategy.name(), strategy.id()),
+    );
+
+    let clear_option = CLEARABLE_SETTINGS.iter().fold(
+        CreateCommandOption::new(CommandOptionType::String, "clear", "Setting to clear back to its default"),
+        |option, setting| option.add_string_choice(*setting, *setting),
+    );
+
+    CreateCommandOption::new(CommandOptionType::SubCommand, "edit", "Edits a creator channel")
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "creator_id", "Creator channel to edit")
+                .set_autocomplete(true)
+                .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Channel, "category_id", "Category for the temporary channel to be created in")
+                .channel_types(vec![ChannelType::Category]),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "naming_standard", "Naming standard")
+                .max_length(MAX_CHANNEL_NAME_LENGTH as u16)
+                .set_autocomplete(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "user_limit", "User limit")
+                .min_int_value(0)
+                .max_int_value(MAX_USER_LIMIT as u64),
+        )
+        .add_sub_option(permission_preset_option())
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "max_channels", "Most temporary channels at once, 0 for no limit")
+                .min_int_value(0)
+                .max_int_value(MAX_GUILD_CHANNELS as u64),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "limit_message", "Message sent to members who join when the limit is reached")
+                .max_length(MAX_LIMIT_MESSAGE_LENGTH as u16),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "max_owned_channels", "Most temporary channels one member can own, 1 by default")
+                .min_int_value(1)
+                .max_int_value(MAX_GUILD_CHANNELS as u64),
+        )
+        .add_sub_option(numbering_strategy_option)
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "number_min", "Lowest number a channel can be given")
+                .min_int_value(1)
+                .max_int_value(u16::MAX as u64),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "number_max", "Highest number a channel can be given")
+                .min_int_value(1)
+                .max_int_value(u16::MAX as u64),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "number_pool", "Share numbers with every creator channel using this pool")
+                .max_length(MAX_NUMBER_POOL_NAME_LENGTH as u16)
+                .set_autocomplete(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Boolean, "companion_text", "Create a private text channel for the members of each channel"),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "grace_period", "Seconds an empty channel is kept before it is deleted")
+                .min_int_value(0)
+                .max_int_value(MAX_GRACE_PERIOD as u64),
+        )
+        .add_sub_option(position_strategy_option)
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Boolean, "live_rename", "Rename channels when the activity of their owner changes"),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Boolean, "stable_room", "Always give a member the same %room% from this creator channel"),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "bitrate", "Bitrate of created channels in bits per second")
+                .min_int_value(MIN_BITRATE as u64)
+                .max_int_value(MAX_BITRATE as u64),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "rtc_region", "Voice region of created channels")
+                .set_autocomplete(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "video_quality", "Video quality of created channels")
+                .add_string_choice("Auto", "auto")
+                .add_string_choice("720p", "full"),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Boolean, "nsfw", "Whether created channels are age restricted"),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "slowmode", "Slowmode of created channels in seconds")
+                .min_int_value(0)
+                .max_int_value(MAX_SLOWMODE as u64),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "status", "Voice channel status of created channels")
+                .max_length(MAX_STATUS_LENGTH as u16),
+        )
+        .add_sub_option(clear_option)
+}
+
+pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteractionResponse {
+    let edit_option = match command.data.options.iter().find(|opt| opt.name == "edit") {
+        None => return create_response("Something went wrong when trying to parse the command options!"),
+        Some(command_data_option) => command_data_option,
+    };
+
+    let sub_options = match &edit_option.value {
+        CommandDataOptionValue::SubCommand(options) => options,
+        CommandDataOptionValue::SubCommandGroup(options) => options,
+        _ => return create_response("Invalid subcommand or subcommand group format!"),
+    };
+
+    let option_map: HashMap<&str, &CommandDataOptionValue> = HashMap::from_iter(
+        sub_options.iter().map(|opt| (opt.name.as_str(), &opt.value))
+    );
+
+    let creator_id = match option_map.get("creator_id").and_then(|value| value.as_str()).and_then(parse_channel_id) {
+        None => return create_response("Pick a creator channel from the suggestions!"),
+        Some(channel_id) => channel_id,
+    };
+
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    let mut creator_channel = match storage.get_creator_voice_config(&creator_id).await {
+        Some(creator_channel) if Some(creator_channel.guild_id) == command.guild_id => creator_channel,
+        _ => return create_response("That channel is not a creator channel!"),
+    };
+
+    if let Err(why) = apply_options(ctx, command, &option_map, &mut creator_channel).await {
+        return create_response(&why);
+    }
+
+    storage.set_creator_voice_config(&creator_channel).await;
+
+    if option_map.contains_key("naming_standard") {
+        return create_response(&format!(
+            "Updated the creator channel! Channels will now be named like:\n{}",
+            render_examples(ctx, command, creator_channel.guild_id, Some(creator_channel.creator_id), &creator_channel.naming_standard).await
+        ));
+    }
+
+    create_response("Updated the creator channel!")
+}
+
+/// Validates every given option before changing the config so a failed edit changes nothing
+async fn apply_options(
+    ctx: &Context,
+    command: &CommandInteraction,
+    option_map: &HashMap<&str, &CommandDataOptionValue>,
+    creator_channel: &mut CreatorChannelConfig,
+) -> Result<(), String> {
+    let guild_id = creator_channel.guild_id;
+    let mut edited = creator_channel.clone();
+
+    if let Some(category_id) = option_map.get("category_id").and_then(|value| value.as_channel_id()) {
+        validate_channel_kind(ctx, guild_id, category_id, ChannelType::Category, "category_id").await?;
+        edited.category_id = category_id;
+    }
+
+    if let Some(naming_standard) = option_map.get("naming_standard").and_then(|value| value.as_str()) {
+        validate_naming_standard(ctx, command, guild_id, Some(edited.creator_id), naming_standard).await?;
+        edited.naming_standard = naming_standard.to_string();
+    }
+
+    if let Some(user_limit) = option_map.get("user_limit").and_then(|value| value.as_i64()) {
+        edited.user_limit = validate_user_limit(user_limit)?;
+    }
+
+    if let Some(permission_preset) = option_map.get("permission_preset").and_then(|value| value.as_str()) {
+        edited.permission_preset = validate_permission_preset(permission_preset)?;
+    }
+
+    if let Some(max_channels) = option_map.get("max_channels").and_then(|value| value.as_i64()) {
+        edited.max_channels = validate_max_channels(max_channels)?;
+    }
+
+    if let Some(limit_message) = option_map.get("limit_message").and_then(|value| value.as_str()) {
+        edited.limit_message = Some(limit_message.to_string());
+    }
+
+    if let Some(max_owned_channels) = option_map.get("max_owned_channels").and_then(|value| value.as_i64()) {
+        edited.max_owned_channels = Some(validate_max_owned_channels(max_owned_channels)?);
+    }
+
+    if let Some(numbering_strategy) = option_map.get("numbering_strategy").and_then(|value| value.as_str()) {
+        edited.numbering_strategy = validate_numbering_strategy(numbering_strategy)?;
+    }
+
+    let number_min = option_map.get("number_min").and_then(|value| value.as_i64());
+    let number_max = option_map.get("number_max").and_then(|value| value.as_i64());
+
+    if number_min.is_some() || number_max.is_some() {
+        edited.number_range = Some(validate_number_range(edited.number_range, number_min, number_max)?);
+    }
+
+    if let Some(number_pool) = option_map.get("number_pool").and_then(|value| value.as_str()) {
+        edited.number_pool = Some(validate_number_pool(number_pool)?);
+    }
+
+    if let Some(companion_text) = option_map.get("companion_text").and_then(|value| value.as_bool()) {
+        edited.companion_text = companion_text;
+    }
+
+    if let Some(grace_period) = option_map.get("grace_period").and_then(|value| value.as_i64()) {
+        edited.grace_period = validate_grace_period(grace_period)?;
+    }
+
+    if let Some(position_strategy) = option_map.get("position_strategy").and_then(|value| value.as_str()) {
+        edited.position_strategy = validate_position_strategy(position_strategy)?;
+    }
+
+    if let Some(live_rename) = option_map.get("live_rename").and_then(|value| value.as_bool()) {
+        edited.live_rename = live_rename;
+    }
+
+    if let Some(stable_room) = option_map.get("stable_room").and_then(|value| value.as_bool()) {
+        edited.stable_room = stable_room;
+    }
+
+    let properties = &mut edited.channel_properties;
+
+    if let Some(bitrate) = option_map.get("bitrate").and_then(|value| value.as_i64()) {
+        properties.bitrate = Some(validate_bitrate(bitrate)?);
+    }
+
+    if let Some(rtc_region) = option_map.get("rtc_region").and_then(|value| value.as_str()) {
+        properties.rtc_region = Some(validate_rtc_region(ctx, rtc_region).await?);
+    }
+
+    if let Some(video_quality) = option_map.get("video_quality").and_then(|value| value.as_str()) {
+        properties.video_quality_mode = match video_quality {
+            "auto" => Some(VideoQualityMode::Auto),
+            "full" => Some(VideoQualityMode::Full),
+            _ => return Err(format!("`{}` is not a video quality!", video_quality)),
+        };
+    }
+
+    if let Some(nsfw) = option_map.get("nsfw").and_then(|value| value.as_bool()) {
+        properties.nsfw = Some(nsfw);
+    }
+
+    if let Some(slowmode) = option_map.get("slowmode").and_then(|value| value.as_i64()) {
+        properties.slowmode = Some(validate_slowmode(slowmode)?);
+    }
+
+    if let Some(status) = option_map.get("status").and_then(|value| value.as_str()) {
+        properties.status = Some(status.to_string());
+    }
+
+    if let Some(setting) = option_map.get("clear").and_then(|value| value.as_str()) {
+        match setting {
+            "bitrate" => properties.bitrate = None,
+            "rtc_region" => properties.rtc_region = None,
+            "video_quality" => properties.video_quality_mode = None,
+            "nsfw" => properties.nsfw = None,
+            "slowmode" => properties.slowmode = None,
+            "status" => properties.status = None,
+            "number_range" => edited.number_range = None,
+            "number_pool" => edited.number_pool = None,
+            _ => return Err(format!("`{}` can not be cleared!", setting)),
+        }
+    }
+
+    validate_number_range_limit(edited.number_range, edited.max_channels)?;
+
+    *creator_channel = edited;
+
+    Ok(())
+}
+
+fn create_response(string: &str) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(string)
+    )
+}
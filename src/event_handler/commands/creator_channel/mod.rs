@@ -1,8 +1,17 @@
 mod reset;
 mod add;
+mod autocomplete;
+mod category;
+mod edit;
+mod preview;
+mod role;
+mod validation;
 
-use serenity::all::{CommandInteraction, Context, CreateInteractionResponse, CreateInteractionResponseMessage, Permissions};
-use serenity::builder::CreateCommand;
+use serenity::all::{CommandInteraction, Context, CreateAutocompleteResponse, CreateInteractionResponse, CreateInteractionResponseMessage, Permissions};
+use serenity::all::CommandOptionType;
+use serenity::builder::{CreateCommand, CreateCommandOption};
+
+use crate::permission_preset::PermissionPreset;
 
 pub fn register() -> CreateCommand {
     CreateCommand::new("creator-channel")
@@ -11,9 +20,21 @@ pub fn register() -> CreateCommand {
         .add_option(
             add::get_command_option()
         )
+        .add_option(
+            edit::get_command_option()
+        )
         .add_option(
             reset::get_command_option()
         )
+        .add_option(
+            role::get_command_option()
+        )
+        .add_option(
+            category::get_command_option()
+        )
+        .add_option(
+            preview::get_command_option()
+        )
 }
 
 pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteractionResponse {
@@ -24,11 +45,27 @@ pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteracti
 
     match option.name.as_str() {
         "add" => add::run(ctx, command).await,
+        "edit" => edit::run(ctx, command).await,
         "reset" => reset::run(ctx, command).await,
+        "role" => role::run(ctx, command).await,
+        "category" => category::run(ctx, command).await,
+        "preview" => preview::run(ctx, command).await,
         _ => create_response("Unknown subcommand!"),
     }
 }
 
+pub async fn autocomplete(ctx: &Context, command: &CommandInteraction) -> CreateAutocompleteResponse {
+    autocomplete::run(ctx, command).await
+}
+
+/// Option to pick the permissions owners and the users they invite get
+fn permission_preset_option() -> CreateCommandOption {
+    PermissionPreset::ALL.iter().fold(
+        CreateCommandOption::new(CommandOptionType::String, "permission_preset", "Permissions the channel owner gets"),
+        |option, preset| option.add_string_choice(preset.name(), preset.id()),
+    )
+}
+
 fn create_response(string: &str) -> CreateInteractionResponse {
     CreateInteractionResponse::Message(
         CreateInteractionResponseMessage::new()
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::num::NonZeroU16;
+
+use rand_chacha::ChaCha8Rng;
+use serenity::all::{ChannelId, CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateInteractionResponse, CreateInteractionResponseMessage, GuildId};
+use serenity::builder::CreateCommandOption;
+
+use crate::event_handler::commands::creator_channel::validation::{validate_naming_standard, MAX_CHANNEL_NAME_LENGTH};
+use crate::guild_config::GuildConfig;
+use crate::room_words::get_room_rng;
+use crate::template::{get_name_from_template, TemplateContext};
+use crate::StorageKey;
+
+/// How many example names are shown when previewing a naming standard
+const EXAMPLE_COUNT: u16 = 5;
+
+/// Used for `%creator%` when previewing without a creator channel
+const EXAMPLE_CREATOR_NAME: &str = "Join to Create";
+
+pub fn get_command_option() -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::SubCommand, "preview", "Previews the channel names a naming standard produces")
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "naming_standard", "Naming standard")
+                .max_length(MAX_CHANNEL_NAME_LENGTH as u16)
+                .set_autocomplete(true)
+                .required(true),
+        )
+}
+
+pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteractionResponse {
+    let guild_id = match command.guild_id {
+        None => return create_response("This command can only be used in a server."),
+        Some(guild_id) => guild_id,
+    };
+
+    let preview_option = match command.data.options.iter().find(|opt| opt.name == "preview") {
+        None => return create_response("Something went wrong when trying to parse the command options!"),
+        Some(command_data_option) => command_data_option,
+    };
+
+    let sub_options = match &preview_option.value {
+        CommandDataOptionValue::SubCommand(options) => options,
+        CommandDataOptionValue::SubCommandGroup(options) => options,
+        _ => return create_response("Invalid subcommand or subcommand group format!"),
+    };
+
+    let option_map: HashMap<&str, &CommandDataOptionValue> = HashMap::from_iter(
+        sub_options.iter().map(|opt| (opt.name.as_str(), &opt.value))
+    );
+
+    let naming_standard = match option_map.get("naming_standard").and_then(|value| value.as_str()) {
+        None => return create_response("Something went wrong when trying to parse the command options!"),
+        Some(naming_standard) => naming_standard,
+    };
+
+    if let Err(why) = validate_naming_standard(ctx, command, guild_id, None, naming_standard).await {
+        return create_response(&why);
+    }
+
+    create_response(&format!(
+        "Preview of `{}`:\n{}",
+        naming_standard,
+        render_examples(ctx, command, guild_id, None, naming_standard).await
+    ))
+}
+
+/// Renders the naming standard as if the member running the command joined the creator channel
+pub(super) async fn render_for_member(
+    ctx: &Context,
+    command: &CommandInteraction,
+    guild_id: GuildId,
+    creator_id: Option<ChannelId>,
+    naming_standard: &str,
+    number: NonZeroU16,
+) -> String {
+    let (guild_config, stable_room) = get_configs(ctx, guild_id, creator_id).await;
+    let context = get_member_context(ctx, command, &guild_config, creator_id, stable_room, number);
+
+    get_name_from_template(naming_standard, &context)
+}
+
+/// Renders a few examples with increasing numbers
+pub(super) async fn render_examples(
+    ctx: &Context,
+    command: &CommandInteraction,
+    guild_id: GuildId,
+    creator_id: Option<ChannelId>,
+    naming_standard: &str,
+) -> String {
+    let (guild_config, stable_room) = get_configs(ctx, guild_id, creator_id).await;
+    let context = get_member_context(ctx, command, &guild_config, creator_id, stable_room, NonZeroU16::MIN);
+
+    format_examples(naming_standard, &guild_config, context, || {
+        get_room_rng(stable_room, command.user.id, get_room_channel_id(command, creator_id))
+    })
+}
+
+/// Renders one example per number, each example picks its `%room%` with a new generator from
+/// `room_rng` so a stable room stays the same and any other room changes
+fn format_examples(
+    naming_standard: &str,
+    guild_config: &GuildConfig,
+    mut context: TemplateContext,
+    room_rng: impl Fn() -> ChaCha8Rng,
+) -> String {
+    (1..=EXAMPLE_COUNT)
+        .filter_map(NonZeroU16::new)
+        .map(|number| {
+            context.number = number;
+            context.room = Some(guild_config.get_room(context.user_name, &mut room_rng()));
+            format!("- {}", get_name_from_template(naming_standard, &context))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The server settings and whether the creator channel picks the same `%room%` every time
+async fn get_configs(ctx: &Context, guild_id: GuildId, creator_id: Option<ChannelId>) -> (GuildConfig, bool) {
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    let guild_config = storage
+        .get_guild_config(&guild_id)
+        .await
+        .unwrap_or_else(|| GuildConfig::new(guild_id));
+
+    let stable_room = match creator_id {
+        None => false,
+        Some(creator_id) => storage
+            .get_creator_voice_config(&creator_id)
+            .await
+            .is_some_and(|config| config.stable_room),
+    };
+
+    (guild_config, stable_room)
+}
+
+/// The channel a stable `%room%` is seeded with. Without a creator channel the room is never stable
+/// so the seed does not matter
+fn get_room_channel_id(command: &CommandInteraction, creator_id: Option<ChannelId>) -> ChannelId {
+    creator_id.unwrap_or(command.channel_id)
+}
+
+/// The context the member running the command would get when joining the creator channel
+fn get_member_context<'a>(
+    ctx: &Context,
+    command: &'a CommandInteraction,
+    guild_config: &GuildConfig,
+    creator_id: Option<ChannelId>,
+    stable_room: bool,
+    number: NonZeroU16,
+) -> TemplateContext<'a> {
+    let mut context = match &command.member {
+        None => TemplateContext::new(number, command.user.display_name()),
+        Some(member) => TemplateContext::for_member(ctx, member, number),
+    };
+
+    let creator_name = creator_id
+        .and_then(|creator_id| {
+            let guild = guild_config.guild_id.to_guild_cached(ctx)?;
+            guild.channels.get(&creator_id).map(|channel| channel.name.clone())
+        })
+        .unwrap_or_else(|| EXAMPLE_CREATOR_NAME.to_string());
+
+    let mut room_rng = get_room_rng(stable_room, command.user.id, get_room_channel_id(command, creator_id));
+
+    context.creator_name = Some(creator_name);
+    context.member_count = Some(1);
+    context.now = Some(guild_config.get_current_time());
+    context.activity_priority = guild_config.activity_priority.clone();
+    context.room = Some(guild_config.get_room(context.user_name, &mut room_rng));
+    context
+}
+
+fn create_response(string: &str) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(string)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event_handler::commands::creator_channel::preview::format_examples;
+    use crate::guild_config::GuildConfig;
+    use crate::room_words::get_room_rng;
+    use crate::template::TemplateContext;
+    use serenity::all::{ChannelId, GuildId, UserId};
+    use std::collections::HashSet;
+    use std::num::NonZeroU16;
+
+    #[test]
+    fn check_example_numbering() {
+        let guild_config = GuildConfig::new(GuildId::new(1));
+        let examples = format_examples(
+            "%name%'s Channel #%number%",
+            &guild_config,
+            TemplateContext::new(NonZeroU16::MIN, "Inbound"),
+            || get_room_rng(false, UserId::new(1), ChannelId::new(2)),
+        );
+
+        assert_eq!(
+            examples,
+            "- Inbound's Channel #1\n- Inbound's Channel #2\n- Inbound's Channel #3\n- Inbound's Channel #4\n- Inbound's Channel #5"
+        );
+    }
+
+    #[test]
+    fn check_example_without_activity() {
+        let guild_config = GuildConfig::new(GuildId::new(1));
+        let examples = format_examples(
+            "%current_activity%",
+            &guild_config,
+            TemplateContext::new(NonZeroU16::MIN, "Inbound"),
+            || get_room_rng(false, UserId::new(1), ChannelId::new(2)),
+        );
+
+        assert!(examples.lines().all(|line| line == "- No Game"));
+    }
+
+    #[test]
+    fn check_example_stable_room() {
+        let guild_config = GuildConfig::new(GuildId::new(1));
+        let examples = format_examples(
+            "%name%'s %room% #%number%",
+            &guild_config,
+            TemplateContext::new(NonZeroU16::MIN, "Inbound"),
+            || get_room_rng(true, UserId::new(1), ChannelId::new(2)),
+        );
+
+        assert_eq!(
+            examples,
+            "- Inbound's Institute #1\n- Inbound's Institute #2\n- Inbound's Institute #3\n- Inbound's Institute #4\n- Inbound's Institute #5"
+        );
+
+        // Without a stable room the examples show more than one room sooner or later
+        let rooms = (0..20)
+            .flat_map(|_| {
+                let examples = format_examples(
+                    "%room%",
+                    &guild_config,
+                    TemplateContext::new(NonZeroU16::MIN, "Inbound"),
+                    || get_room_rng(false, UserId::new(1), ChannelId::new(2)),
+                );
+                examples.lines().map(str::to_string).collect::<Vec<String>>()
+            })
+            .collect::<HashSet<String>>();
+        assert!(rooms.len() > 1);
+    }
+}
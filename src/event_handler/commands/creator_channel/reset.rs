@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use serenity::all::{CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateInteractionResponse, CreateInteractionResponseMessage};
 use serenity::builder::CreateCommandOption;
+use crate::event_handler::commands::creator_channel::autocomplete::parse_channel_id;
 use crate::StorageKey;
 
 macro_rules! extract_option {
@@ -19,7 +20,8 @@ macro_rules! extract_option {
 pub fn get_command_option() -> CreateCommandOption {
     CreateCommandOption::new(CommandOptionType::SubCommand, "reset", "Resets a creator channel")
         .add_sub_option(
-            CreateCommandOption::new(CommandOptionType::Channel, "creator_id", "Channel to be reset")
+            CreateCommandOption::new(CommandOptionType::String, "creator_id", "Creator channel to be reset")
+                .set_autocomplete(true)
                 .required(true),
         )
 }
@@ -39,8 +41,8 @@ pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteracti
     let option_map: HashMap<&str, &CommandDataOptionValue> = HashMap::from_iter(
         sub_options.iter().map(|opt| (opt.name.as_str(), &opt.value))
     );
-    let creator_id = match extract_option!(option_map, "creator_id", as_channel_id) {
-        None => return create_response("Something went wrong when trying to parse the command options!"),
+    let creator_id = match extract_option!(option_map, "creator_id", as_str).and_then(parse_channel_id) {
+        None => return create_response("Pick a creator channel from the suggestions!"),
         Some(channel_id) => channel_id,
     };
 
@@ -56,15 +58,25 @@ pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteracti
     };
 
     let mut creator_channel = match storage.get_creator_voice_config(&creator_id).await {
-        None => return create_response("That channel is not a creator channel!"),
-        Some(creator_channel) => creator_channel,
+        Some(creator_channel) if Some(creator_channel.guild_id) == command.guild_id => creator_channel,
+        _ => return create_response("That channel is not a creator channel!"),
     };
 
     creator_channel.reset();
 
     storage.set_creator_voice_config(&creator_channel).await;
 
+    if let Some(pool_name) = &creator_channel.number_pool {
+        if let Some(mut number_pool) = storage.get_number_pool(&creator_channel.guild_id, pool_name).await {
+            number_pool.numbers.reset();
+            storage.set_number_pool(&number_pool).await;
 
+            return create_response(&format!(
+                "Reset completed successfully! The number pool `{}` was reset for every creator channel using it.",
+                pool_name
+            ));
+        }
+    }
 
     create_response("Reset completed successfully!")
 }
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateInteractionResponse, CreateInteractionResponseMessage, Mentionable, RoleId};
+use serenity::builder::CreateCommandOption;
+
+use crate::event_handler::commands::creator_channel::autocomplete::parse_channel_id;
+use crate::StorageKey;
+
+pub fn get_command_option() -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::SubCommand, "role", "Changes which roles can use a creator channel")
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "creator_id", "Creator channel to change")
+                .set_autocomplete(true)
+                .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::String, "list", "Whether the role is allowed or denied")
+                .add_string_choice("Allowed", "allowed")
+                .add_string_choice("Denied", "denied")
+                .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Role, "role", "Role to add to the list")
+                .required(true),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(CommandOptionType::Boolean, "remove", "Remove the role from the list instead"),
+        )
+}
+
+pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteractionResponse {
+    let role_option = match command.data.options.iter().find(|opt| opt.name == "role") {
+        None => return create_response("Something went wrong when trying to parse the command options!"),
+        Some(command_data_option) => command_data_option,
+    };
+
+    let sub_options = match &role_option.value {
+        CommandDataOptionValue::SubCommand(options) => options,
+        CommandDataOptionValue::SubCommandGroup(options) => options,
+        _ => return create_response("Invalid subcommand or subcommand group format!"),
+    };
+
+    let option_map: HashMap<&str, &CommandDataOptionValue> = HashMap::from_iter(
+        sub_options.iter().map(|opt| (opt.name.as_str(), &opt.value))
+    );
+
+    let creator_id = match option_map.get("creator_id").and_then(|value| value.as_str()).and_then(parse_channel_id) {
+        None => return create_response("Pick a creator channel from the suggestions!"),
+        Some(channel_id) => channel_id,
+    };
+
+    let list = match option_map.get("list").and_then(|value| value.as_str()) {
+        None => return create_response("Something went wrong when trying to parse the command options!"),
+        Some(list) => list,
+    };
+
+    let role_id = match option_map.get("role").and_then(|value| value.as_role_id()) {
+        None => return create_response("Something went wrong when trying to parse the command options!"),
+        Some(role_id) => role_id,
+    };
+
+    let remove = option_map
+        .get("remove")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    let mut creator_channel = match storage.get_creator_voice_config(&creator_id).await {
+        Some(creator_channel) if Some(creator_channel.guild_id) == command.guild_id => creator_channel,
+        _ => return create_response("That channel is not a creator channel!"),
+    };
+
+    let role_ids = match list {
+        "allowed" => &mut creator_channel.allowed_role_ids,
+        "denied" => &mut creator_channel.denied_role_ids,
+        _ => return create_response(&format!("`{}` is not a role list!", list)),
+    };
+
+    if remove {
+        role_ids.retain(|listed_role_id| *listed_role_id != role_id);
+    } else if !role_ids.contains(&role_id) {
+        role_ids.push(role_id);
+    }
+
+    storage.set_creator_voice_config(&creator_channel).await;
+
+    create_response(&format!(
+        "Updated the creator channel!\nAllowed roles: {}\nDenied roles: {}",
+        format_roles(&creator_channel.allowed_role_ids, "Everyone"),
+        format_roles(&creator_channel.denied_role_ids, "None"),
+    ))
+}
+
+fn format_roles(role_ids: &[RoleId], empty: &str) -> String {
+    if role_ids.is_empty() {
+        return empty.to_string();
+    }
+
+    role_ids
+        .iter()
+        .map(|role_id| role_id.mention().to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn create_response(string: &str) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(string)
+    )
+}
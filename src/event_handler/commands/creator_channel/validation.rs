@@ -0,0 +1,332 @@
+use serenity::all::{ChannelId, ChannelType, CommandInteraction, Context, GuildId};
+
+use crate::event_handler::commands::creator_channel::preview::render_for_member;
+use crate::creator_channel::{NumberRange, NumberingStrategy};
+use crate::permission_preset::PermissionPreset;
+use crate::position_strategy::PositionStrategy;
+use crate::template::Template;
+use std::num::{NonZero, NonZeroU16};
+
+/// Discord does not allow a user limit above this for voice channels
+pub(super) const MAX_USER_LIMIT: i64 = 99;
+
+/// Discord allows channel names of at most this many characters
+pub(super) const MAX_CHANNEL_NAME_LENGTH: usize = 100;
+
+pub(super) async fn validate_channel_kind(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    kind: ChannelType,
+    option_name: &str,
+) -> Result<(), String> {
+    let channels = match guild_id.channels(ctx).await {
+        Err(_) => return Err("Could not get the channels of this server, please try again!".to_string()),
+        Ok(channels) => channels,
+    };
+
+    let channel = match channels.get(&channel_id) {
+        None => return Err(format!("`{}` must be a channel in this server!", option_name)),
+        Some(channel) => channel,
+    };
+
+    if channel.kind != kind {
+        return Err(format!("`{}` must be a {} channel!", option_name, kind.name()));
+    }
+
+    Ok(())
+}
+
+pub(super) fn validate_user_limit(user_limit: i64) -> Result<u32, String> {
+    if !(0..=MAX_USER_LIMIT).contains(&user_limit) {
+        return Err(format!("`user_limit` must be between 0 and {}!", MAX_USER_LIMIT));
+    }
+
+    Ok(user_limit as u32)
+}
+
+/// Checks the naming standard only uses known placeholders and renders it against the member
+/// running the command to make sure it produces a usable channel name
+pub(super) async fn validate_naming_standard(
+    ctx: &Context,
+    command: &CommandInteraction,
+    guild_id: GuildId,
+    creator_id: Option<ChannelId>,
+    naming_standard: &str,
+) -> Result<(), String> {
+    Template::parse(naming_standard).map_err(|why| why.to_string())?;
+
+    let number = NonZeroU16::new(1).expect("This should never be 0");
+    let name = render_for_member(ctx, command, guild_id, creator_id, naming_standard, number).await;
+
+    validate_channel_name(&name)
+}
+
+/// Checks a channel name the naming standard produced is one Discord accepts
+fn validate_channel_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("`naming_standard` must not produce an empty channel name!".to_string());
+    }
+
+    if name.chars().count() > MAX_CHANNEL_NAME_LENGTH {
+        return Err(format!(
+            "`naming_standard` must produce a channel name of at most {} characters, `{}` is too long!",
+            MAX_CHANNEL_NAME_LENGTH, name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Lowest bitrate Discord allows for voice channels
+pub(super) const MIN_BITRATE: i64 = 8000;
+
+/// Highest bitrate Discord allows for voice channels, only available in boosted servers
+pub(super) const MAX_BITRATE: i64 = 384000;
+
+/// Discord does not allow a slowmode above six hours
+pub(super) const MAX_SLOWMODE: i64 = 21600;
+
+/// Discord does not allow voice channel statuses longer than this
+pub(super) const MAX_STATUS_LENGTH: usize = 500;
+
+pub(super) fn validate_bitrate(bitrate: i64) -> Result<u32, String> {
+    if !(MIN_BITRATE..=MAX_BITRATE).contains(&bitrate) {
+        return Err(format!("`bitrate` must be between {} and {}!", MIN_BITRATE, MAX_BITRATE));
+    }
+
+    Ok(bitrate as u32)
+}
+
+pub(super) fn validate_slowmode(slowmode: i64) -> Result<u16, String> {
+    if !(0..=MAX_SLOWMODE).contains(&slowmode) {
+        return Err(format!("`slowmode` must be between 0 and {} seconds!", MAX_SLOWMODE));
+    }
+
+    Ok(slowmode as u16)
+}
+
+pub(super) async fn validate_rtc_region(ctx: &Context, rtc_region: &str) -> Result<String, String> {
+    let regions = match ctx.http.get_voice_regions().await {
+        Err(_) => return Err("Could not get the voice regions, please try again!".to_string()),
+        Ok(regions) => regions,
+    };
+
+    match regions.iter().find(|region| region.id == rtc_region) {
+        None => Err(format!("`{}` is not a voice region!", rtc_region)),
+        Some(region) => Ok(region.id.clone()),
+    }
+}
+
+/// Discord does not allow a server to have more channels than this
+pub(super) const MAX_GUILD_CHANNELS: i64 = 500;
+
+/// Keeps limit messages well within Discord's message length limit
+pub(super) const MAX_LIMIT_MESSAGE_LENGTH: usize = 1000;
+
+/// A limit of 0 means there is no limit
+pub(super) fn validate_max_channels(max_channels: i64) -> Result<Option<u16>, String> {
+    if !(0..=MAX_GUILD_CHANNELS).contains(&max_channels) {
+        return Err(format!("`max_channels` must be between 0 and {}!", MAX_GUILD_CHANNELS));
+    }
+
+    match max_channels {
+        0 => Ok(None),
+        max_channels => Ok(Some(max_channels as u16)),
+    }
+}
+
+pub(super) fn validate_max_owned_channels(max_owned_channels: i64) -> Result<u16, String> {
+    if !(1..=MAX_GUILD_CHANNELS).contains(&max_owned_channels) {
+        return Err(format!("`max_owned_channels` must be between 1 and {}!", MAX_GUILD_CHANNELS));
+    }
+
+    Ok(max_owned_channels as u16)
+}
+
+pub(super) fn validate_permission_preset(permission_preset: &str) -> Result<PermissionPreset, String> {
+    match PermissionPreset::from_id(permission_preset) {
+        None => Err(format!("`{}` is not a permission preset!", permission_preset)),
+        Some(permission_preset) => Ok(permission_preset),
+    }
+}
+
+pub(super) fn validate_numbering_strategy(numbering_strategy: &str) -> Result<NumberingStrategy, String> {
+    match NumberingStrategy::from_id(numbering_strategy) {
+        None => Err(format!("`{}` is not a numbering strategy!", numbering_strategy)),
+        Some(numbering_strategy) => Ok(numbering_strategy),
+    }
+}
+
+pub(super) fn validate_position_strategy(position_strategy: &str) -> Result<PositionStrategy, String> {
+    match PositionStrategy::from_id(position_strategy) {
+        None => Err(format!("`{}` is not a position strategy!", position_strategy)),
+        Some(position_strategy) => Ok(position_strategy),
+    }
+}
+
+/// Combines the given ends of the range with the current range, an end that is not given keeps its
+/// current value or is as wide as possible
+pub(super) fn validate_number_range(
+    current: Option<NumberRange>,
+    min: Option<i64>,
+    max: Option<i64>,
+) -> Result<NumberRange, String> {
+    let to_number = |value: i64, option_name: &str| -> Result<NonZeroU16, String> {
+        u16::try_from(value)
+            .ok()
+            .and_then(NonZeroU16::new)
+            .ok_or_else(|| format!("`{}` must be between 1 and {}!", option_name, u16::MAX))
+    };
+
+    let min = match min {
+        Some(min) => to_number(min, "number_min")?,
+        None => current.map_or(NonZero::new(1).expect("This should never be 0"), |range| range.min),
+    };
+
+    let max = match max {
+        Some(max) => to_number(max, "number_max")?,
+        None => current.map_or(NonZeroU16::MAX, |range| range.max),
+    };
+
+    if min > max {
+        return Err(format!("`number_min` ({}) must not be above `number_max` ({})!", min, max));
+    }
+
+    Ok(NumberRange { min, max })
+}
+
+/// Longest grace period in seconds before an empty channel is deleted
+pub(super) const MAX_GRACE_PERIOD: i64 = 3600;
+
+pub(super) fn validate_grace_period(grace_period: i64) -> Result<Option<u16>, String> {
+    match grace_period {
+        0 => Ok(None),
+        1..=MAX_GRACE_PERIOD => Ok(Some(grace_period as u16)),
+        _ => Err(format!("The grace period must be between 0 and {} seconds!", MAX_GRACE_PERIOD)),
+    }
+}
+
+/// Longest name a number pool can have
+pub(super) const MAX_NUMBER_POOL_NAME_LENGTH: usize = 32;
+
+pub(super) fn validate_number_pool(number_pool: &str) -> Result<String, String> {
+    let number_pool = number_pool.trim();
+
+    if number_pool.is_empty() || number_pool.chars().count() > MAX_NUMBER_POOL_NAME_LENGTH {
+        return Err(format!(
+            "The number pool name must be between 1 and {} characters!",
+            MAX_NUMBER_POOL_NAME_LENGTH
+        ));
+    }
+
+    Ok(number_pool.to_string())
+}
+
+/// Numbers never go above the channel limit, so a range has to start at or below it
+pub(super) fn validate_number_range_limit(number_range: Option<NumberRange>, max_channels: Option<u16>) -> Result<(), String> {
+    match (number_range, max_channels) {
+        (Some(number_range), Some(max_channels)) if number_range.min.get() > max_channels => Err(format!(
+            "`number_min` ({}) must not be above `max_channels` ({})!",
+            number_range.min, max_channels
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::creator_channel::NumberRange;
+    use crate::event_handler::commands::creator_channel::validation::{
+        validate_bitrate, validate_channel_name, validate_grace_period, validate_max_channels,
+        validate_max_owned_channels, validate_number_range, validate_number_range_limit, validate_permission_preset,
+        validate_slowmode, validate_user_limit, MAX_BITRATE, MAX_CHANNEL_NAME_LENGTH, MAX_GRACE_PERIOD, MAX_SLOWMODE,
+        MIN_BITRATE,
+    };
+    use crate::permission_preset::PermissionPreset;
+    use std::num::NonZeroU16;
+
+    #[test]
+    fn check_user_limit() {
+        assert_eq!(validate_user_limit(0), Ok(0));
+        assert_eq!(validate_user_limit(99), Ok(99));
+        assert!(validate_user_limit(-1).is_err());
+        assert!(validate_user_limit(100).is_err());
+    }
+
+    #[test]
+    fn check_bitrate() {
+        assert_eq!(validate_bitrate(MIN_BITRATE), Ok(8000));
+        assert_eq!(validate_bitrate(MAX_BITRATE), Ok(384000));
+        assert!(validate_bitrate(MIN_BITRATE - 1).is_err());
+        assert!(validate_bitrate(MAX_BITRATE + 1).is_err());
+    }
+
+    #[test]
+    fn check_slowmode() {
+        assert_eq!(validate_slowmode(0), Ok(0));
+        assert_eq!(validate_slowmode(MAX_SLOWMODE), Ok(21600));
+        assert!(validate_slowmode(-1).is_err());
+        assert!(validate_slowmode(MAX_SLOWMODE + 1).is_err());
+    }
+
+    #[test]
+    fn check_max_channels() {
+        assert_eq!(validate_max_channels(0), Ok(None));
+        assert_eq!(validate_max_channels(1), Ok(Some(1)));
+        assert_eq!(validate_max_channels(500), Ok(Some(500)));
+        assert!(validate_max_channels(-1).is_err());
+        assert!(validate_max_channels(501).is_err());
+    }
+
+    #[test]
+    fn check_max_owned_channels() {
+        assert_eq!(validate_max_owned_channels(1), Ok(1));
+        assert_eq!(validate_max_owned_channels(500), Ok(500));
+        assert!(validate_max_owned_channels(0).is_err());
+        assert!(validate_max_owned_channels(501).is_err());
+    }
+
+    #[test]
+    fn check_number_range() {
+        let number = |number: u16| NonZeroU16::new(number).unwrap();
+        let range = NumberRange { min: number(5), max: number(10) };
+
+        assert_eq!(validate_number_range(None, Some(5), Some(10)), Ok(range));
+        assert_eq!(validate_number_range(Some(range), Some(7), None), Ok(NumberRange { min: number(7), max: number(10) }));
+        assert_eq!(validate_number_range(None, None, Some(10)), Ok(NumberRange { min: number(1), max: number(10) }));
+        assert!(validate_number_range(Some(range), Some(11), None).is_err());
+        assert!(validate_number_range(None, Some(0), None).is_err());
+
+        assert!(validate_number_range_limit(Some(range), Some(5)).is_ok());
+        assert!(validate_number_range_limit(Some(range), Some(4)).is_err());
+        assert!(validate_number_range_limit(Some(range), None).is_ok());
+        assert!(validate_number_range_limit(None, Some(1)).is_ok());
+    }
+
+    #[test]
+    fn check_grace_period() {
+        assert_eq!(validate_grace_period(0), Ok(None));
+        assert_eq!(validate_grace_period(30), Ok(Some(30)));
+        assert_eq!(validate_grace_period(MAX_GRACE_PERIOD), Ok(Some(3600)));
+        assert!(validate_grace_period(-1).is_err());
+        assert!(validate_grace_period(MAX_GRACE_PERIOD + 1).is_err());
+    }
+
+    #[test]
+    fn check_permission_preset() {
+        assert_eq!(validate_permission_preset("moderate_owner"), Ok(PermissionPreset::ModerateOwner));
+        assert!(validate_permission_preset("Moderate owner").is_err());
+        assert!(validate_permission_preset("").is_err());
+    }
+
+    #[test]
+    fn check_channel_name() {
+        assert!(validate_channel_name("Inbound's Inn").is_ok());
+        assert!(validate_channel_name(&"a".repeat(MAX_CHANNEL_NAME_LENGTH)).is_ok());
+        assert!(validate_channel_name(&"a".repeat(MAX_CHANNEL_NAME_LENGTH + 1)).is_err());
+        assert!(validate_channel_name(&"🎮".repeat(MAX_CHANNEL_NAME_LENGTH)).is_ok());
+        assert!(validate_channel_name("").is_err());
+        assert!(validate_channel_name("   ").is_err());
+    }
+}
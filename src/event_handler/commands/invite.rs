@@ -1,4 +1,5 @@
 use crate::event_handler::cool_down_manager::CooldownManager;
+use crate::permission_preset::PermissionPreset;
 use crate::StorageKey;
 use serenity::all::{ChannelId, CommandDataOption, CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateInteractionResponse, CreateInteractionResponseMessage, GuildId, Mentionable, Message, PermissionOverwrite, PermissionOverwriteType, Permissions, User, UserId, VoiceState};
 use serenity::builder::{CreateCommand, CreateCommandOption, CreateMessage};
@@ -25,14 +26,14 @@ pub async fn run(
         Some(guild_id) => guild_id,
     };
 
-    let invited_user = match get_invited_user(&command) {
+    let invited_user = match get_invited_user(command) {
         None => return ephemeral_response("You must mention a user to invite."),
         Some(user_id) => user_id,
     };
 
     // Try to check cooldown before making a request to discord's servers
     let inviter = &command.user;
-    let is_command_on_cooldown = !cooldown_manager.can_user_ping_user(&inviter.id, &invited_user);
+    let is_command_on_cooldown = !cooldown_manager.can_user_ping_user(&inviter.id, invited_user);
     if is_command_on_cooldown {
         return ephemeral_response("Please wait as you have already pinged this person!");
     }
@@ -75,9 +76,17 @@ pub async fn run(
 
     let temporary_voice_channel = storage.get_temporary_voice_channel(&voice_channel_id).await;
 
-    let is_owner_of_voice_channel = match temporary_voice_channel {
-        None => false,
-        Some(temporary_voice_channel) => temporary_voice_channel.owner_id == inviter.id,
+    let (is_owner_of_voice_channel, permission_preset) = match temporary_voice_channel {
+        None => (false, PermissionPreset::default()),
+        Some(temporary_voice_channel) => {
+            let permission_preset = storage
+                .get_creator_voice_config(&temporary_voice_channel.creator_id)
+                .await
+                .map(|config| config.permission_preset)
+                .unwrap_or_default();
+
+            (temporary_voice_channel.owner_id == inviter.id, permission_preset)
+        }
     };
 
     let guild_channel = match voice_channel_id.to_channel(ctx).await {
@@ -85,16 +94,11 @@ pub async fn run(
         Err(_) => None,
     };
 
-    if is_owner_of_voice_channel {
+    if let (true, Some(invite_permissions)) = (is_owner_of_voice_channel, permission_preset.invite_permissions()) {
         let permissions = PermissionOverwrite {
-            allow: Permissions::VIEW_CHANNEL
-                | Permissions::MOVE_MEMBERS // This permission lets the invited user join even if the voice channel is full
-                | Permissions::CONNECT
-                | Permissions::SPEAK
-                | Permissions::SEND_MESSAGES
-                | Permissions::READ_MESSAGE_HISTORY,
+            allow: invite_permissions,
             deny: Permissions::empty(),
-            kind: PermissionOverwriteType::Member(invited_user.clone()),
+            kind: PermissionOverwriteType::Member(*invited_user),
         };
 
         let _ = voice_channel_id.create_permission(ctx, permissions).await;
@@ -144,7 +148,7 @@ fn is_invited_user_in_same_voice_channel(
             return false;
         };
 
-        return invited_user.get() == user_id.get();
+        invited_user.get() == user_id.get()
     })
 }
 
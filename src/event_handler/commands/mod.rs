@@ -1,2 +1,3 @@
 pub mod invite;
-pub mod creator_channel;
\ No newline at end of file
+pub mod creator_channel;
+pub mod server_settings;
\ No newline at end of file
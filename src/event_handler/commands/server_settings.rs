@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use chrono_tz::{Tz, TZ_VARIANTS};
+use serenity::all::{Attachment, CommandDataOptionValue, CommandInteraction, CommandOptionType, Context, CreateAutocompleteResponse, CreateInteractionResponse, CreateInteractionResponseMessage, Permissions};
+use serenity::builder::{CreateCommand, CreateCommandOption};
+
+use crate::activity_priority::ActivityKind;
+use crate::guild_config::GuildConfig;
+use crate::room_words::{RoomLocale, RoomWords};
+use crate::StorageKey;
+
+/// Discord shows at most this many autocomplete choices
+const MAX_CHOICES: usize = 25;
+
+/// Most activity names that can be ignored
+const MAX_IGNORED_ACTIVITIES: usize = 25;
+
+/// Clears the ignored activities when given as `ignored_activities`
+const CLEAR_IGNORED_ACTIVITIES: &str = "none";
+
+/// Largest word list file in bytes that is downloaded
+const MAX_ROOM_WORDS_FILE_SIZE: u32 = 64 * 1024;
+
+pub fn register() -> CreateCommand {
+    let room_locale_option = RoomLocale::ALL.iter().fold(
+        CreateCommandOption::new(CommandOptionType::String, "room_locale", "Language %room% is picked in"),
+        |option, locale| option.add_string_choice(locale.name(), locale.id()),
+    );
+
+    CreateCommand::new("server-settings")
+        .description("Changes settings for every creator channel in this server")
+        .default_member_permissions(Permissions::ADMINISTRATOR)
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "timezone", "Time zone %date% and %time% are shown in, like Europe/Amsterdam")
+                .set_autocomplete(true),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "activity_priority",
+            "Activity kinds %current_activity% shows in order, like: playing, streaming, listening",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::String,
+            "ignored_activities",
+            "Activity names that are never shown, like: Steam, Battle.net (none to clear)",
+        ))
+        .add_option(room_locale_option)
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::Attachment,
+            "room_words",
+            "A JSON or TOML file with the words %room% is picked from for each letter",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::Boolean,
+            "reset_room_words",
+            "Go back to the words of the room locale",
+        ))
+}
+
+pub async fn run(ctx: &Context, command: &CommandInteraction) -> CreateInteractionResponse {
+    let guild_id = match command.guild_id {
+        None => return create_response("This command can only be used in a server."),
+        Some(guild_id) => guild_id,
+    };
+
+    let option_map: HashMap<&str, &CommandDataOptionValue> = HashMap::from_iter(
+        command.data.options.iter().map(|opt| (opt.name.as_str(), &opt.value))
+    );
+
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    let mut guild_config = storage
+        .get_guild_config(&guild_id)
+        .await
+        .unwrap_or_else(|| GuildConfig::new(guild_id));
+
+    if let Some(timezone) = option_map.get("timezone").and_then(|value| value.as_str()) {
+        match timezone.trim().parse::<Tz>() {
+            Err(_) => return create_response(&format!("`{}` is not a known time zone!", timezone)),
+            Ok(timezone) => guild_config.timezone = Some(timezone.name().to_string()),
+        }
+    }
+
+    if let Some(activity_priority) = option_map.get("activity_priority").and_then(|value| value.as_str()) {
+        match validate_activity_priority(activity_priority) {
+            Err(why) => return create_response(&why),
+            Ok(order) => guild_config.activity_priority.order = order,
+        }
+    }
+
+    if let Some(ignored_activities) = option_map.get("ignored_activities").and_then(|value| value.as_str()) {
+        match validate_ignored_activities(ignored_activities) {
+            Err(why) => return create_response(&why),
+            Ok(ignored) => guild_config.activity_priority.ignored = ignored,
+        }
+    }
+
+    if let Some(room_locale) = option_map.get("room_locale").and_then(|value| value.as_str()) {
+        match RoomLocale::from_id(room_locale) {
+            None => return create_response(&format!("`{}` is not a room locale!", room_locale)),
+            Some(room_locale) => guild_config.room_locale = room_locale,
+        }
+    }
+
+    if let Some(true) = option_map.get("reset_room_words").and_then(|value| value.as_bool()) {
+        guild_config.room_words = None;
+    }
+
+    if let Some(attachment_id) = option_map.get("room_words").and_then(|value| value.as_attachment_id()) {
+        let attachment = match command.data.resolved.attachments.get(&attachment_id) {
+            None => return create_response("Could not find the uploaded word list, please try again!"),
+            Some(attachment) => attachment,
+        };
+
+        match load_room_words(attachment).await {
+            Err(why) => return create_response(&why),
+            Ok(room_words) => guild_config.room_words = Some(room_words),
+        }
+    }
+
+    storage.set_guild_config(&guild_config).await;
+
+    let room_words = match &guild_config.room_words {
+        None => guild_config.room_locale.name().to_string(),
+        Some(room_words) => format!(
+            "{} uploaded words, then {}",
+            room_words.word_count(),
+            guild_config.room_locale.name()
+        ),
+    };
+
+    let ignored = match guild_config.activity_priority.ignored.is_empty() {
+        true => "Nothing".to_string(),
+        false => guild_config.activity_priority.ignored.join(", "),
+    };
+
+    create_response(&format!(
+        "Updated the server settings!\nTime zone: {} (it is {} there now)\nActivity priority: {}\nIgnored activities: {}\nRoom words: {}",
+        guild_config.get_timezone().name(),
+        guild_config.get_current_time().format("%H:%M"),
+        guild_config
+            .activity_priority
+            .order
+            .iter()
+            .map(|kind| kind.name())
+            .collect::<Vec<&str>>()
+            .join(" > "),
+        ignored,
+        room_words,
+    ))
+}
+
+/// Downloads and parses an uploaded word list, files ending in `.toml` are read as TOML and anything else as JSON
+async fn load_room_words(attachment: &Attachment) -> Result<RoomWords, String> {
+    if attachment.size > MAX_ROOM_WORDS_FILE_SIZE {
+        return Err(format!("The word list must be smaller than {} KiB!", MAX_ROOM_WORDS_FILE_SIZE / 1024));
+    }
+
+    let bytes = match attachment.download().await {
+        Err(_) => return Err("Could not download the word list, please try again!".to_string()),
+        Ok(bytes) => bytes,
+    };
+
+    let source = match String::from_utf8(bytes) {
+        Err(_) => return Err("The word list must be a UTF-8 text file!".to_string()),
+        Ok(source) => source,
+    };
+
+    match attachment.filename.to_lowercase().ends_with(".toml") {
+        true => RoomWords::parse_toml(&source),
+        false => RoomWords::parse_json(&source),
+    }
+}
+
+/// Parses a comma separated list of activity kinds, kinds that are left out are never shown
+fn validate_activity_priority(activity_priority: &str) -> Result<Vec<ActivityKind>, String> {
+    let mut order = Vec::new();
+
+    for id in activity_priority.split(',').map(|id| id.trim().to_lowercase()).filter(|id| !id.is_empty()) {
+        let kind = ActivityKind::from_id(&id).ok_or_else(|| {
+            format!(
+                "`{}` is not an activity kind, use any of: {}",
+                id,
+                ActivityKind::ALL.map(|kind| kind.id()).join(", ")
+            )
+        })?;
+
+        if !order.contains(&kind) {
+            order.push(kind);
+        }
+    }
+
+    if order.is_empty() {
+        return Err("`activity_priority` must contain at least one activity kind!".to_string());
+    }
+
+    Ok(order)
+}
+
+fn validate_ignored_activities(ignored_activities: &str) -> Result<Vec<String>, String> {
+    if ignored_activities.trim().eq_ignore_ascii_case(CLEAR_IGNORED_ACTIVITIES) {
+        return Ok(Vec::new());
+    }
+
+    let mut ignored: Vec<String> = Vec::new();
+
+    for name in ignored_activities.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        if !ignored.iter().any(|ignored| ignored.eq_ignore_ascii_case(name)) {
+            ignored.push(name.to_string());
+        }
+    }
+
+    if ignored.len() > MAX_IGNORED_ACTIVITIES {
+        return Err(format!("At most {} activities can be ignored!", MAX_IGNORED_ACTIVITIES));
+    }
+
+    Ok(ignored)
+}
+
+pub async fn autocomplete(command: &CommandInteraction) -> CreateAutocompleteResponse {
+    let focused_option = match command.data.autocomplete() {
+        Some(focused_option) if focused_option.name == "timezone" => focused_option,
+        _ => return CreateAutocompleteResponse::new(),
+    };
+
+    let search = focused_option.value.trim().to_lowercase();
+
+    TZ_VARIANTS
+        .iter()
+        .map(|timezone| timezone.name())
+        .filter(|name| name.to_lowercase().contains(&search))
+        .take(MAX_CHOICES)
+        .fold(CreateAutocompleteResponse::new(), |response, name| {
+            response.add_string_choice(name, name)
+        })
+}
+
+fn create_response(string: &str) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(string)
+    )
+}
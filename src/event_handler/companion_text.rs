@@ -0,0 +1,96 @@
+use serenity::all::{ChannelId, ChannelType, Context, CreateChannel, GuildId, PermissionOverwrite, PermissionOverwriteType, RoleId, UserId};
+use serenity::model::Permissions;
+
+use crate::temporary_channel::TemporaryVoiceChannel;
+
+/// Permissions the owner and the members in the voice channel get on the companion text channel
+fn member_permissions() -> Permissions {
+    Permissions::VIEW_CHANNEL
+        | Permissions::SEND_MESSAGES
+        | Permissions::READ_MESSAGE_HISTORY
+        | Permissions::ATTACH_FILES
+        | Permissions::EMBED_LINKS
+}
+
+/// Creates a private text channel next to a temporary voice channel that only the owner can see
+pub(super) async fn create_companion_text_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    category_id: ChannelId,
+    name: &str,
+    owner_id: UserId,
+) -> Option<ChannelId> {
+    let permissions = vec![
+        // The @everyone role has the same id as the guild
+        PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId::new(guild_id.get())),
+        },
+        PermissionOverwrite {
+            allow: member_permissions() | Permissions::MANAGE_MESSAGES,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(ctx.cache.current_user().id),
+        },
+        PermissionOverwrite {
+            allow: member_permissions(),
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(owner_id),
+        },
+    ];
+
+    let builder = CreateChannel::new(name)
+        .kind(ChannelType::Text)
+        .category(category_id)
+        .permissions(permissions)
+        .audit_log_reason("Temp voice bot");
+
+    match guild_id.create_channel(&ctx.http, builder).await {
+        Ok(text_channel) => Some(text_channel.id),
+        Err(why) => {
+            println!("Could not create companion text channel: {:?}", why);
+            None
+        }
+    }
+}
+
+/// Lets a member who joined the voice channel see its companion text channel
+pub(super) async fn grant_companion_text_access(ctx: &Context, temp_channel: &TemporaryVoiceChannel, user_id: UserId) {
+    let text_channel_id = match temp_channel.text_channel_id {
+        None => return,
+        Some(text_channel_id) => text_channel_id,
+    };
+
+    let overwrite = PermissionOverwrite {
+        allow: member_permissions(),
+        deny: Permissions::empty(),
+        kind: PermissionOverwriteType::Member(user_id),
+    };
+
+    if let Err(why) = text_channel_id.create_permission(ctx, overwrite).await {
+        println!("Could not give access to companion text channel: {:?}", why);
+    }
+}
+
+/// Hides the companion text channel from a member who left the voice channel, the owner keeps access
+pub(super) async fn revoke_companion_text_access(ctx: &Context, temp_channel: &TemporaryVoiceChannel, user_id: UserId) {
+    let text_channel_id = match temp_channel.text_channel_id {
+        Some(text_channel_id) if user_id != temp_channel.owner_id => text_channel_id,
+        _ => return,
+    };
+
+    if let Err(why) = text_channel_id
+        .delete_permission(ctx, PermissionOverwriteType::Member(user_id))
+        .await
+    {
+        println!("Could not remove access to companion text channel: {:?}", why);
+    }
+}
+
+pub(super) async fn delete_companion_text_channel(ctx: &Context, temp_channel: &TemporaryVoiceChannel) {
+    if let Some(text_channel_id) = temp_channel.text_channel_id {
+        if let Err(why) = text_channel_id.delete(ctx).await {
+            println!("Could not delete companion text channel: {:?}", why);
+        }
+    }
+}
@@ -20,7 +20,7 @@ impl CooldownManager {
 
         let now = Instant::now();
         let mut user_lock = self.user_cooldowns.lock().unwrap();
-        let mut user_cooldowns = user_lock.entry(user_id.clone()).or_insert_with(HashMap::new);
+        let user_cooldowns = user_lock.entry(*user_id).or_default();
 
         if let Some(last_ping_time) = user_cooldowns.get(user_id_to_ping) {
             if now.duration_since(*last_ping_time) < DELAY {
@@ -30,6 +30,6 @@ impl CooldownManager {
 
         user_cooldowns.insert(*user_id_to_ping, now);
 
-        return true;
+        true
     }
 }
\ No newline at end of file
@@ -1,29 +1,54 @@
+use std::collections::HashMap;
+use std::num::NonZeroU16;
 use std::sync::Arc;
 
+mod activity_tracker;
 mod commands;
+mod companion_text;
 mod cool_down_manager;
+mod ownership;
+mod pending_deletion;
+mod rename_manager;
 
+use crate::creator_channel::CreatorChannelConfig;
+use crate::event_handler::activity_tracker::ActivityTracker;
+use crate::event_handler::companion_text::{create_companion_text_channel, delete_companion_text_channel, grant_companion_text_access, revoke_companion_text_access};
 use crate::event_handler::cool_down_manager::CooldownManager;
+use crate::event_handler::ownership::transfer_ownership;
+use crate::event_handler::pending_deletion::{cancel_deletion, get_member_count, reconcile_pending_deletions, schedule_deletion};
+use crate::event_handler::rename_manager::RenameManager;
+use crate::guild_config::GuildConfig;
+use crate::number_pool::NumberPool;
+use crate::room_words::get_room_rng;
 use crate::storage::Storage;
-use crate::temporary_channel::{get_name_from_template, get_user_presence, TemporaryVoiceChannel};
+use crate::template::{get_name_from_template, TemplateContext};
+use crate::temporary_channel::TemporaryVoiceChannel;
 use crate::StorageKey;
 use async_trait::async_trait;
 use serenity::all::{
-    Channel, ChannelId, ChannelType, Command, Context, CreateChannel, CreateInteractionResponse,
+    Channel, ChannelId, ChannelType, Command, Context, GuildId, CreateAutocompleteResponse, CreateChannel,
+    CreateInteractionResponse,
     EditChannel, EventHandler, GuildChannel, Interaction, Member, Message, PermissionOverwrite,
-    PermissionOverwriteType, Ready, VoiceState,
+    PermissionOverwriteType, Presence, Ready, RoleId, VoiceState,
 };
-use serenity::builder::CreateInteractionResponseMessage;
+use serenity::builder::{CreateInteractionResponseMessage, CreateMessage};
 use serenity::model::Permissions;
 
+/// Discord does not allow more channels than this in one category
+const MAX_CATEGORY_CHANNELS: usize = 50;
+
 pub(crate) struct Handler {
     cooldown_manager: CooldownManager,
+    rename_manager: RenameManager,
+    activity_tracker: ActivityTracker,
 }
 
 impl Handler {
     pub fn new() -> Self {
         Self {
             cooldown_manager: CooldownManager::new(),
+            rename_manager: RenameManager::new(),
+            activity_tracker: ActivityTracker::new(),
         }
     }
 }
@@ -34,7 +59,7 @@ impl EventHandler for Handler {
         &self,
         ctx: Context,
         deleted_channel: GuildChannel,
-        messages: Option<Vec<Message>>,
+        _messages: Option<Vec<Message>>,
     ) {
         let storage = {
             let data_read = ctx.data.read().await;
@@ -57,7 +82,7 @@ impl EventHandler for Handler {
                 }
             }
             Some(temporary_channel) => {
-                remove_deleted_temporary_channel(&storage, &temporary_channel, &deleted_channel).await;
+                remove_deleted_temporary_channel(&ctx, &storage, &temporary_channel).await;
             }
         }
     }
@@ -70,6 +95,7 @@ impl EventHandler for Handler {
             vec![
                 commands::invite::register(),
                 commands::creator_channel::register(),
+                commands::server_settings::register(),
             ],
         )
         .await
@@ -78,6 +104,58 @@ impl EventHandler for Handler {
         println!("{} is ready!", ready.user.name);
     }
 
+    async fn cache_ready(&self, ctx: Context, guilds: Vec<GuildId>) {
+        reconcile_pending_deletions(&ctx, &guilds).await;
+    }
+
+    async fn presence_update(&self, ctx: Context, new_data: Presence) {
+        let guild_id = match new_data.guild_id {
+            None => return,
+            Some(guild_id) => guild_id,
+        };
+
+        // Going idle or offline does not change the name of the channel
+        if !self.activity_tracker.activities_changed(guild_id, &new_data) {
+            return;
+        }
+
+        let storage = {
+            let data_read = ctx.data.read().await;
+            match data_read.get::<StorageKey>().cloned() {
+                None => {
+                    println!("Storage is null!");
+                    panic!()
+                }
+                Some(storage) => storage,
+            }
+        };
+
+        let owned_channels = storage
+            .get_owned_temporary_voice_channels(&guild_id, &new_data.user.id)
+            .await
+            .unwrap_or_default();
+
+        let mut live_renames: HashMap<ChannelId, bool> = HashMap::new();
+
+        for owned_channel in owned_channels {
+            let live_rename = match live_renames.get(&owned_channel.creator_id) {
+                Some(live_rename) => *live_rename,
+                None => {
+                    let live_rename = storage
+                        .get_creator_voice_config(&owned_channel.creator_id)
+                        .await
+                        .is_some_and(|config| config.live_rename);
+                    live_renames.insert(owned_channel.creator_id, live_rename);
+                    live_rename
+                }
+            };
+
+            if live_rename {
+                self.rename_manager.request_rename(&ctx, owned_channel.channel_id);
+            }
+        }
+    }
+
     async fn voice_state_update(
         &self,
         ctx: Context,
@@ -109,15 +187,43 @@ impl EventHandler for Handler {
             }
         };
 
+        let old_channel_id = old_voice_state
+            .as_ref()
+            .and_then(|old_voice_state| old_voice_state.channel_id);
+
+        // The channel a refused member was moved back into, it must not be treated as left
+        let mut moved_back_to = None;
+
         // Member joins a voice channel
-        if new_voice_state.channel_id.is_some() {
-            match on_voice_channel_join(&ctx, &storage, member, new_voice_state.channel_id.unwrap())
+        if let Some(new_channel_id) = new_voice_state.channel_id {
+            match on_voice_channel_join(&ctx, &storage, member, new_channel_id)
                 .await
             {
-                None => {} // This means they did not join a creator channel
+                // This means they did not join a creator channel
+                None => {
+                    if let Some(mut temp_channel) = storage.get_temporary_voice_channel(&new_channel_id).await {
+                        cancel_deletion(storage.as_ref(), &mut temp_channel).await;
+                        grant_companion_text_access(&ctx, &temp_channel, member.user.id).await;
+                        self.rename_manager.request_member_count_rename(&ctx, &temp_channel);
+                    }
+                }
                 Some(result) => match result {
                     Ok(_channel) => {}
-                    Err(why) => {
+                    Err(JoinError::Refused(reason)) => {
+                        moved_back_to = refuse_join(&ctx, member, old_channel_id, &reason).await;
+                    }
+                    Err(JoinError::Redirected(owned_channel_id)) => {
+                        match member.move_to_voice_channel(&ctx, owned_channel_id).await {
+                            Ok(_) => moved_back_to = Some(owned_channel_id),
+                            Err(_) => {
+                                println!("Failed to move member into their own channel");
+                                if member.disconnect_from_voice(&ctx).await.is_err() {
+                                    println!("Failed to disconnect from voice channel");
+                                }
+                            }
+                        }
+                    }
+                    Err(JoinError::Failed(why)) => {
                         println!("Error joining channel: {:?}", why);
                         match member.disconnect_from_voice(&ctx).await {
                             Ok(_) => {}
@@ -131,18 +237,31 @@ impl EventHandler for Handler {
         }
 
         // Member leaves a voice channel
-        if old_voice_state.is_some() {
-            on_voice_channel_leave(&ctx, &storage, old_voice_state.unwrap()).await;
+        if let Some(old_voice_state) = old_voice_state {
+            if moved_back_to.is_none() || old_voice_state.channel_id != moved_back_to {
+                on_voice_channel_leave(&ctx, &storage, &self.rename_manager, old_voice_state).await;
+            }
         }
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Command(command) = interaction {
+        if let Interaction::Autocomplete(autocomplete) = interaction {
+            let response = match autocomplete.data.name.as_str() {
+                "creator-channel" => commands::creator_channel::autocomplete(&ctx, &autocomplete).await,
+                "server-settings" => commands::server_settings::autocomplete(&autocomplete).await,
+                _ => CreateAutocompleteResponse::new(),
+            };
+
+            let _ = autocomplete
+                .create_response(ctx, CreateInteractionResponse::Autocomplete(response))
+                .await;
+        } else if let Interaction::Command(command) = interaction {
             let command_name = command.data.name.as_str();
 
             let response = match command_name {
                 "invite" => commands::invite::run(&ctx, &command, &self.cooldown_manager).await,
                 "creator-channel" => commands::creator_channel::run(&ctx, &command).await,
+                "server-settings" => commands::server_settings::run(&ctx, &command).await,
                 _ => CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
                         .ephemeral(true)
@@ -158,98 +277,198 @@ impl EventHandler for Handler {
     }
 }
 
+/// Why joining a creator channel did not give the member a new channel
+enum JoinError {
+    /// The member is not allowed a new channel, they are moved back and told the reason
+    Refused(String),
+    /// The member owns as many channels as they are allowed, they are moved into this one instead
+    Redirected(ChannelId),
+    /// Something went wrong while creating the channel
+    Failed(&'static str),
+}
+
 async fn on_voice_channel_join(
     ctx: &Context,
-    storage: &Arc<impl Storage + Send + Sync + ?Sized>,
+    storage: &Arc<impl Storage + ?Sized>,
     member: &Member,
     creator_channel_id: ChannelId,
-) -> Option<Result<GuildChannel, &'static str>> {
+) -> Option<Result<GuildChannel, JoinError>> {
     let mut config = storage
         .get_creator_voice_config(&creator_channel_id)
         .await?;
 
+    if !config.is_member_allowed(&member.roles) {
+        return Some(Err(JoinError::Refused(get_role_message(ctx, &config))));
+    }
+
+    let owned_channels = get_owned_channels(ctx, storage.as_ref(), &config, member).await;
+
+    if owned_channels.len() >= config.get_max_owned_channels() as usize {
+        // Prefer a channel that was made by the creator channel they joined
+        let owned_channel = owned_channels
+            .iter()
+            .find(|owned_channel| owned_channel.creator_id == creator_channel_id)
+            .or(owned_channels.first());
+
+        if let Some(owned_channel) = owned_channel {
+            return Some(Err(JoinError::Redirected(owned_channel.channel_id)));
+        }
+    }
+
+    let mut number_pool = match &config.number_pool {
+        None => None,
+        Some(pool_name) => Some(
+            storage
+                .get_number_pool(&config.guild_id, pool_name)
+                .await
+                .unwrap_or_else(|| NumberPool::new(config.guild_id, pool_name.clone())),
+        ),
+    };
+
+    // The numbers of a pool are shared with other creator channels so only this creator's channels count
+    let channel_count = match number_pool {
+        None => config.numbers.channel_numbers.len(),
+        Some(_) => get_creator_channel_count(ctx, storage, &config).await,
+    };
+
+    if config.is_full(channel_count) {
+        return Some(Err(JoinError::Refused(config.get_limit_message())));
+    }
+
     let voice_channel_owner = member.user.clone();
     let voice_channel_owner_id = voice_channel_owner.id;
-    let voice_channel_owner_name = member.display_name();
 
     let naming_standard = config.naming_standard.clone();
 
     let guild_id = config.guild_id;
 
-    let number = config.get_next_number();
-    let user_presence = get_user_presence(ctx, &guild_id, &voice_channel_owner_id);
+    let next_number = match &number_pool {
+        None => config.get_next_number(),
+        Some(number_pool) => config.get_next_number_from(&number_pool.numbers),
+    };
+
+    let number = match next_number {
+        Ok(number) => number,
+        Err(why) => return Some(Err(JoinError::Refused(why.to_string()))),
+    };
+
+    let guild_channels = match guild_id.channels(ctx).await {
+        Err(_) => return Some(Err(JoinError::Failed("Could not get guild channels"))),
+        Ok(hash_map) => hash_map,
+    };
 
-    let channel_name = get_name_from_template(
-        &naming_standard,
-        &number,
-        user_presence,
-        voice_channel_owner_name,
+    let creator_channel = match guild_channels.get(&creator_channel_id) {
+        None => return Some(Err(JoinError::Failed("Could not get the creator channel"))),
+        Some(guild_channel) => guild_channel.clone(),
+    };
+
+    let guild_config = storage
+        .get_guild_config(&guild_id)
+        .await
+        .unwrap_or_else(|| GuildConfig::new(guild_id));
+
+    let room = guild_config.get_room(
+        member.display_name(),
+        &mut get_room_rng(config.stable_room, member.user.id, creator_channel_id),
     );
 
-    let creator_channel = match guild_id.channels(ctx).await {
-        Err(_) => return Some(Err("Could not get guild channels")),
-        Ok(hash_map) => match hash_map.get(&creator_channel_id) {
-            None => return Some(Err("Could not get the creator channel")),
-            Some(guild_channel) => guild_channel.clone(),
-        },
+    let mut template_context = TemplateContext::for_member(ctx, member, number);
+    template_context.creator_name = Some(creator_channel.name.clone());
+    // The owner is the only member when the channel is created
+    template_context.member_count = Some(1);
+    template_context.now = Some(guild_config.get_current_time());
+    template_context.activity_priority = guild_config.activity_priority;
+    template_context.room = Some(room.clone());
+
+    let channel_name = get_name_from_template(&naming_standard, &template_context);
+
+    let category_id = match get_category_with_room(ctx, storage, &mut config, &guild_channels).await {
+        Ok(category_id) => category_id,
+        Err(why) => return Some(Err(why)),
     };
 
     let mut permissions_overrides = creator_channel.permission_overwrites.clone();
 
-    permissions_overrides.push(PermissionOverwrite {
-        allow: Permissions::MOVE_MEMBERS
-            | Permissions::MANAGE_CHANNELS
-            | Permissions::MANAGE_ROLES
-            | Permissions::VIEW_CHANNEL
-            | Permissions::CONNECT
-            | Permissions::SPEAK
-            | Permissions::PRIORITY_SPEAKER
-            | Permissions::SEND_MESSAGES
-            | Permissions::READ_MESSAGE_HISTORY,
-
-        deny: Permissions::empty(),
-        kind: PermissionOverwriteType::Member(member.user.id),
-    });
+    if let Some(owner_permissions) = config.permission_preset.owner_permissions() {
+        permissions_overrides.push(PermissionOverwrite {
+            allow: owner_permissions,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(member.user.id),
+        });
+    }
 
-    let bitrate = creator_channel.bitrate.unwrap_or_else(|| 64000);
-    let nsfw = creator_channel.nsfw;
+    let properties = &config.channel_properties;
+
+    let bitrate = properties.bitrate.or(creator_channel.bitrate).unwrap_or(64000);
+    let nsfw = properties.nsfw.unwrap_or(creator_channel.nsfw);
 
     let user_limit = match creator_channel.user_limit {
         None => config.user_limit,
         Some(user_limit) => user_limit,
     };
 
-    let builder = CreateChannel::new(channel_name.clone())
+    let mut builder = CreateChannel::new(channel_name.clone())
         .kind(ChannelType::Voice)
         .user_limit(user_limit)
-        .category(config.category_id)
-        .position(number.get())
+        .category(category_id)
         .permissions(permissions_overrides)
         .audit_log_reason("Temp voice bot")
         .nsfw(nsfw)
         .bitrate(bitrate);
 
+    if let Some(rtc_region) = properties.rtc_region.clone().or(creator_channel.rtc_region.clone()) {
+        builder = builder.rtc_region(rtc_region);
+    }
+
+    if let Some(video_quality_mode) = properties.video_quality_mode.or(creator_channel.video_quality_mode) {
+        builder = builder.video_quality_mode(video_quality_mode);
+    }
+
+    if let Some(slowmode) = properties.slowmode.or(creator_channel.rate_limit_per_user) {
+        builder = builder.rate_limit_per_user(slowmode);
+    }
+
     // Create the channel
     let channel = match config.guild_id.create_channel(&ctx.http, builder).await {
         Ok(channel) => channel,
-        Err(_) => return Some(Err("Could not create guild channel")),
+        Err(_) => return Some(Err(JoinError::Failed("Could not create guild channel"))),
     };
 
     println!("Created channel: {} with number {}", channel.name, number);
 
     let channel_id = channel.id;
 
+    if let Some(status) = &properties.status {
+        if let Err(why) = channel_id.edit(ctx, EditChannel::new().status(status)).await {
+            println!("Error setting voice channel status: {:?}", why);
+        }
+    }
+
     // Move the member to the new voice channel
-    if let Err(_) = member.move_to_voice_channel(&ctx.http, channel_id).await {
+    if member.move_to_voice_channel(&ctx.http, channel_id).await.is_err() {
         let _ = channel.delete(ctx).await;
-        return Some(Err("Could not move voice channel to creator channel"));
+        return Some(Err(JoinError::Failed("Could not move voice channel to creator channel")));
+    }
+
+    let numbers = match &mut number_pool {
+        None => &mut config.numbers,
+        Some(number_pool) => &mut number_pool.numbers,
+    };
+
+    if !numbers.add_number(number) {
+        return Some(Err(JoinError::Failed("Could not add number to config!")));
     }
 
-    if !config.add_number(number) {
-        return Some(Err("Could not add number to config!"));
+    if let Some(number_pool) = &number_pool {
+        storage.set_number_pool(number_pool).await;
     }
 
-    let temporary_voice_channel = TemporaryVoiceChannel::new(
+    let text_channel_id = match config.companion_text {
+        false => None,
+        true => create_companion_text_channel(ctx, guild_id, category_id, &channel_name, voice_channel_owner_id).await,
+    };
+
+    let mut temporary_voice_channel = TemporaryVoiceChannel::new(
         config.guild_id,
         channel_id,
         creator_channel_id,
@@ -258,37 +477,25 @@ async fn on_voice_channel_join(
         naming_standard,
         number,
     );
+    temporary_voice_channel.number_pool = config.number_pool.clone();
+    temporary_voice_channel.text_channel_id = text_channel_id;
+    temporary_voice_channel.room = Some(room);
 
     storage
         .set_temporary_voice_channel(&temporary_voice_channel)
         .await;
 
-    if let Some(highest_number) = config.get_highest_number() {
-        storage.set_creator_voice_config(&config).await;
-
-        if number == highest_number {
-            let new_position = highest_number.get() + 1;
+    storage.set_creator_voice_config(&config).await;
 
-            let change_creator_channel_position =
-                creator_channel_id.edit(ctx, EditChannel::new().position(new_position));
-
-            match change_creator_channel_position.await {
-                Ok(_) => {}
-                Err(why) => {
-                    println!("Error editing channel positions: {:?}", why);
-                }
-            }
-        }
-    } else {
-        panic!("Highest number not found");
-    }
+    reorder_channels(ctx, storage, &config, category_id, &guild_channels, &channel, number).await;
 
     Some(Ok(channel))
 }
 
 async fn on_voice_channel_leave(
     ctx: &Context,
-    storage: &Arc<impl Storage + Send + Sync + ?Sized>,
+    storage: &Arc<impl Storage + ?Sized>,
+    rename_manager: &RenameManager,
     old_voice_state: VoiceState,
 ) {
     let old_channel_id = match old_voice_state.channel_id {
@@ -296,7 +503,7 @@ async fn on_voice_channel_leave(
         Some(old_channel_id) => old_channel_id,
     };
 
-    let temp_channel = match storage.get_temporary_voice_channel(&old_channel_id).await {
+    let mut temp_channel = match storage.get_temporary_voice_channel(&old_channel_id).await {
         None => return,
         Some(temp_channel) => temp_channel,
     };
@@ -316,42 +523,283 @@ async fn on_voice_channel_leave(
         }
     };
 
-    let guild_id = channel.guild_id;
-    let voice_channel_id = channel.id;
-
-    let member_count = {
-        let guild = match guild_id.to_guild_cached(ctx) {
-            Some(guild) => guild,
-            None => {
-                println!("Failed to retrieve the guild.");
-                return;
-            }
-        };
-
-        let count = guild
-            .voice_states
-            .values()
-            .filter(|vs| vs.channel_id == Some(voice_channel_id))
-            .count();
-
-        count
+    let member_count = match get_member_count(ctx, channel.guild_id, channel.id) {
+        Some(member_count) => member_count,
+        None => {
+            println!("Failed to retrieve the guild.");
+            return;
+        }
     };
 
-    if member_count == 0 {
+    let config = storage.get_creator_voice_config(&temp_channel.creator_id).await;
+    let grace_period = config.as_ref().and_then(|config| config.grace_period);
+
+    if let (0, Some(grace_period)) = (member_count, grace_period) {
+        schedule_deletion(ctx, storage.as_ref(), &mut temp_channel, grace_period).await;
+    } else if member_count == 0 {
         match channel.delete(&ctx.http).await {
-            Ok(deleted_channel) => {
-                remove_deleted_temporary_channel(storage, &temp_channel, &deleted_channel).await;
+            Ok(_) => {
+                remove_deleted_temporary_channel(ctx, storage, &temp_channel).await;
             }
             Err(error) => {
                 println!("Something went very wrong when deleting a channel! {}", error);
                 panic!()
             }
         };
+    } else {
+        let transferred = old_voice_state.user_id == temp_channel.owner_id
+            && transfer_ownership(ctx, storage.as_ref(), &mut temp_channel).await;
+
+        // The name only follows the new owner when the creator channel renames channels
+        if transferred && config.is_some_and(|config| config.live_rename) {
+            rename_manager.request_rename(ctx, temp_channel.channel_id);
+        } else {
+            rename_manager.request_member_count_rename(ctx, &temp_channel);
+        }
+
+        // After the ownership moved on the old owner loses access like any other member
+        revoke_companion_text_access(ctx, &temp_channel, old_voice_state.user_id).await;
+    }
+}
+
+/// Explains which roles the member needs or must not have to use the creator channel
+fn get_role_message(ctx: &Context, config: &CreatorChannelConfig) -> String {
+    let role_names = |role_ids: &Vec<RoleId>| -> String {
+        let guild = config.guild_id.to_guild_cached(ctx);
+
+        role_ids
+            .iter()
+            .map(|role_id| {
+                guild
+                    .as_ref()
+                    .and_then(|guild| guild.roles.get(role_id))
+                    .map(|role| role.name.clone())
+                    .unwrap_or_else(|| role_id.to_string())
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    let mut message = String::from("You are not allowed to create a channel with that creator channel!");
+
+    if !config.allowed_role_ids.is_empty() {
+        message.push_str(&format!(" You need one of these roles: {}.", role_names(&config.allowed_role_ids)));
+    }
+
+    if !config.denied_role_ids.is_empty() {
+        message.push_str(&format!(" Members with these roles can not use it: {}.", role_names(&config.denied_role_ids)));
+    }
+
+    message
+}
+
+/// Temporary channels in the creator channel's server that the member owns and that still exist
+async fn get_owned_channels(
+    ctx: &Context,
+    storage: &(impl Storage + ?Sized),
+    config: &CreatorChannelConfig,
+    member: &Member,
+) -> Vec<TemporaryVoiceChannel> {
+    let temporary_channels = storage
+        .get_owned_temporary_voice_channels(&config.guild_id, &member.user.id)
+        .await
+        .unwrap_or_default();
+
+    let guild = match config.guild_id.to_guild_cached(ctx) {
+        None => return Vec::new(),
+        Some(guild) => guild,
+    };
+
+    temporary_channels
+        .into_iter()
+        .filter(|temporary_channel| guild.channels.contains_key(&temporary_channel.channel_id))
+        .collect()
+}
+
+/// The first category of the creator channel that is not full, when every category is full a new
+/// overflow category is created if the creator channel allows it
+async fn get_category_with_room(
+    ctx: &Context,
+    storage: &Arc<impl Storage + ?Sized>,
+    config: &mut CreatorChannelConfig,
+    guild_channels: &HashMap<ChannelId, GuildChannel>,
+) -> Result<ChannelId, JoinError> {
+    let category_ids = config.get_category_ids();
+
+    // The companion text channel is created in the same category as the voice channel
+    let needed_channels = match config.companion_text {
+        true => 2,
+        false => 1,
+    };
+
+    let category_with_room = category_ids.iter().find(|category_id| {
+        guild_channels
+            .values()
+            .filter(|channel| channel.parent_id == Some(**category_id))
+            .count()
+            + needed_channels
+            <= MAX_CATEGORY_CHANNELS
+    });
+
+    if let Some(category_id) = category_with_room {
+        return Ok(*category_id);
+    }
+
+    if !config.auto_overflow {
+        return Err(JoinError::Refused(
+            "Every category of this creator channel is full, join an existing channel or try again later!".to_string(),
+        ));
     }
+
+    let first_category = match guild_channels.get(&config.category_id) {
+        None => return Err(JoinError::Failed("Could not get the category of the creator channel")),
+        Some(first_category) => first_category,
+    };
+
+    let last_position = category_ids
+        .iter()
+        .filter_map(|category_id| guild_channels.get(category_id))
+        .map(|category| category.position)
+        .max()
+        .unwrap_or(first_category.position);
+
+    let builder = CreateChannel::new(format!("{} {}", first_category.name, category_ids.len() + 1))
+        .kind(ChannelType::Category)
+        .position(last_position + 1)
+        .permissions(first_category.permission_overwrites.clone())
+        .audit_log_reason("Temp voice bot");
+
+    let category = match config.guild_id.create_channel(&ctx.http, builder).await {
+        Ok(category) => category,
+        Err(_) => return Err(JoinError::Failed("Could not create overflow category")),
+    };
+
+    println!("Created overflow category: {}", category.name);
+
+    config.overflow_category_ids.push(category.id);
+    storage.set_creator_voice_config(config).await;
+
+    Ok(category.id)
+}
+
+/// Puts the creator channel and its temporary channels in the category in the order of the position
+/// strategy, starting at the highest spot any of them had so other channels keep their place
+async fn reorder_channels(
+    ctx: &Context,
+    storage: &Arc<impl Storage + ?Sized>,
+    config: &CreatorChannelConfig,
+    category_id: ChannelId,
+    guild_channels: &HashMap<ChannelId, GuildChannel>,
+    new_channel: &GuildChannel,
+    number: NonZeroU16,
+) {
+    let is_in_category = |channel_id: &ChannelId| {
+        guild_channels
+            .get(channel_id)
+            .is_some_and(|channel| channel.parent_id == Some(category_id))
+    };
+
+    let creator_id = Some(config.creator_id).filter(is_in_category);
+
+    let mut temporary_channels: Vec<(NonZeroU16, ChannelId)> = storage
+        .get_creator_temporary_voice_channels(&config.creator_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|temporary_channel| is_in_category(&temporary_channel.channel_id))
+        .map(|temporary_channel| (temporary_channel.number, temporary_channel.channel_id))
+        .collect();
+    temporary_channels.push((number, new_channel.id));
+
+    let channel_ids = match config.position_strategy.order_channels(creator_id, &temporary_channels) {
+        None => return,
+        Some(channel_ids) => channel_ids,
+    };
+
+    let first_position = channel_ids
+        .iter()
+        .filter_map(|channel_id| guild_channels.get(channel_id))
+        .map(|channel| channel.position)
+        .chain([new_channel.position])
+        .min()
+        .unwrap_or_default();
+
+    let positions = channel_ids
+        .into_iter()
+        .enumerate()
+        .map(|(index, channel_id)| (channel_id, first_position as u64 + index as u64));
+
+    if let Err(why) = config.guild_id.reorder_channels(ctx, positions).await {
+        println!("Error editing channel positions: {:?}", why);
+    }
+}
+
+/// How many temporary channels of the creator channel still exist
+async fn get_creator_channel_count(
+    ctx: &Context,
+    storage: &Arc<impl Storage + ?Sized>,
+    config: &CreatorChannelConfig,
+) -> usize {
+    let temporary_channels = storage
+        .get_all_temporary_voice_channels()
+        .await
+        .unwrap_or_default();
+
+    let guild = match config.guild_id.to_guild_cached(ctx) {
+        None => return 0,
+        Some(guild) => guild,
+    };
+
+    temporary_channels
+        .iter()
+        .filter(|temporary_channel| temporary_channel.creator_id == config.creator_id)
+        .filter(|temporary_channel| guild.channels.contains_key(&temporary_channel.channel_id))
+        .count()
+}
+
+/// Tells the member why they did not get a channel and moves them back to the channel they came
+/// from, or disconnects them if they came from nowhere. Returns the channel they were moved back to
+async fn refuse_join(
+    ctx: &Context,
+    member: &Member,
+    old_channel_id: Option<ChannelId>,
+    reason: &str,
+) -> Option<ChannelId> {
+    if let Err(why) = member
+        .user
+        .direct_message(ctx, CreateMessage::new().content(reason))
+        .await
+    {
+        println!("Failed to tell a member why they did not get a channel: {:?}", why);
+    }
+
+    if let Some(old_channel_id) = old_channel_id {
+        if member.move_to_voice_channel(ctx, old_channel_id).await.is_ok() {
+            return Some(old_channel_id);
+        }
+    }
+
+    if member.disconnect_from_voice(ctx).await.is_err() {
+        println!("Failed to disconnect from voice channel");
+    }
+
+    None
 }
 
 /// Assuming that the voice channel is deleted
-async fn remove_deleted_temporary_channel(storage: &Arc<impl Storage + Send + Sync + ?Sized>, temp_channel: &TemporaryVoiceChannel, deleted_channel: &GuildChannel) {
+async fn remove_deleted_temporary_channel(ctx: &Context, storage: &Arc<impl Storage + ?Sized>, temp_channel: &TemporaryVoiceChannel) {
+    delete_companion_text_channel(ctx, temp_channel).await;
+
+    if let Some(pool_name) = &temp_channel.number_pool {
+        if let Some(mut number_pool) = storage.get_number_pool(&temp_channel.guild_id, pool_name).await {
+            number_pool.numbers.remove_number(&temp_channel.number);
+            storage.set_number_pool(&number_pool).await;
+        }
+
+        storage.delete_temporary_voice_channel(&temp_channel.channel_id).await;
+        return;
+    }
+
     match storage
         .get_creator_voice_config(&temp_channel.creator_id)
         .await
@@ -361,11 +809,11 @@ async fn remove_deleted_temporary_channel(storage: &Arc<impl Storage + Send + Sy
             panic!()
         }
         Some(mut creator_channel_config) => {
-            creator_channel_config.remove_number(&temp_channel.number);
+            creator_channel_config.numbers.remove_number(&temp_channel.number);
 
             storage.set_creator_voice_config(&creator_channel_config).await;
 
-            storage.delete_temporary_voice_channel(&deleted_channel.id).await;
+            storage.delete_temporary_voice_channel(&temp_channel.channel_id).await;
         }
     }
 }
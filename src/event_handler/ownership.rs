@@ -0,0 +1,95 @@
+use serenity::all::{Context, PermissionOverwrite, PermissionOverwriteType};
+use serenity::model::Permissions;
+
+use crate::event_handler::get_owned_channels;
+use crate::guild_config::GuildConfig;
+use crate::room_words::get_room_rng;
+use crate::storage::Storage;
+use crate::temporary_channel::TemporaryVoiceChannel;
+
+/// Gives the temporary channel to a member that is still in it after the owner left, the owner
+/// permissions move with it and `%room%` is picked again for the new owner. Bots and members that
+/// already own as many channels as the creator channel allows are passed over.
+/// Returns whether the channel got a new owner
+pub(super) async fn transfer_ownership(
+    ctx: &Context,
+    storage: &(impl Storage + ?Sized),
+    temp_channel: &mut TemporaryVoiceChannel,
+) -> bool {
+    let config = storage.get_creator_voice_config(&temp_channel.creator_id).await;
+
+    let candidates = {
+        let guild = match temp_channel.guild_id.to_guild_cached(ctx) {
+            None => return false,
+            Some(guild) => guild,
+        };
+
+        // Whoever has been in the server the longest, a channel with only bots left keeps its owner
+        let mut members_in_channel = guild
+            .voice_states
+            .values()
+            .filter(|voice_state| voice_state.channel_id == Some(temp_channel.channel_id))
+            .filter(|voice_state| voice_state.user_id != temp_channel.owner_id)
+            .filter_map(|voice_state| guild.members.get(&voice_state.user_id))
+            .filter(|member| !member.user.bot)
+            .collect::<Vec<_>>();
+        members_in_channel.sort_by_key(|member| member.joined_at);
+
+        members_in_channel.into_iter().cloned().collect::<Vec<_>>()
+    };
+
+    let mut new_owner = None;
+
+    for candidate in candidates {
+        if let Some(config) = &config {
+            if get_owned_channels(ctx, storage, config, &candidate).await.len() >= config.get_max_owned_channels() as usize {
+                continue;
+            }
+        }
+
+        new_owner = Some(candidate);
+        break;
+    }
+
+    let new_owner = match new_owner {
+        None => return false,
+        Some(new_owner) => new_owner,
+    };
+
+    let owner_permissions = config
+        .as_ref()
+        .and_then(|config| config.permission_preset.owner_permissions());
+
+    if let Some(owner_permissions) = owner_permissions {
+        let old_owner = PermissionOverwriteType::Member(temp_channel.owner_id);
+
+        if let Err(why) = temp_channel.channel_id.delete_permission(ctx, old_owner).await {
+            println!("Could not remove the permissions of the old owner: {:?}", why);
+        }
+
+        let permission_overwrite = PermissionOverwrite {
+            allow: owner_permissions,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(new_owner.user.id),
+        };
+
+        if let Err(why) = temp_channel.channel_id.create_permission(ctx, permission_overwrite).await {
+            println!("Could not give the new owner their permissions: {:?}", why);
+        }
+    }
+
+    let guild_config = storage
+        .get_guild_config(&temp_channel.guild_id)
+        .await
+        .unwrap_or_else(|| GuildConfig::new(temp_channel.guild_id));
+
+    temp_channel.owner_id = new_owner.user.id;
+    let stable_room = config.is_some_and(|config| config.stable_room);
+    let mut room_rng = get_room_rng(stable_room, new_owner.user.id, temp_channel.creator_id);
+
+    temp_channel.room = Some(guild_config.get_room(new_owner.display_name(), &mut room_rng));
+
+    storage.set_temporary_voice_channel(temp_channel).await;
+
+    true
+}
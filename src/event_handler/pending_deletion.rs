@@ -0,0 +1,148 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serenity::all::{ChannelId, Context, GuildId, HttpError};
+
+use crate::event_handler::remove_deleted_temporary_channel;
+use crate::storage::Storage;
+use crate::temporary_channel::TemporaryVoiceChannel;
+use crate::StorageKey;
+
+/// Discord's error code for a channel that does not exist
+const UNKNOWN_CHANNEL_ERROR_CODE: isize = 10003;
+
+/// Marks an empty temporary channel for deletion once the grace period is over, the deletion is
+/// saved so it still happens after a restart
+pub(super) async fn schedule_deletion(
+    ctx: &Context,
+    storage: &(impl Storage + ?Sized),
+    temp_channel: &mut TemporaryVoiceChannel,
+    grace_period: u16,
+) {
+    let delete_at = get_current_time() + grace_period as u64;
+
+    temp_channel.pending_deletion_at = Some(delete_at);
+    temp_channel.pending_deletion_generation = temp_channel.pending_deletion_generation.wrapping_add(1);
+    storage.set_temporary_voice_channel(temp_channel).await;
+
+    spawn_deletion(ctx, temp_channel.channel_id, delete_at, temp_channel.pending_deletion_generation);
+}
+
+/// Keeps the channel when someone joins it before it is deleted
+pub(super) async fn cancel_deletion(storage: &(impl Storage + ?Sized), temp_channel: &mut TemporaryVoiceChannel) {
+    if temp_channel.pending_deletion_at.take().is_some() {
+        storage.set_temporary_voice_channel(temp_channel).await;
+    }
+}
+
+/// Schedules the deletions that were pending when the bot stopped, overdue ones are deleted right away
+pub(super) async fn reconcile_pending_deletions(ctx: &Context, guild_ids: &[GuildId]) {
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    let temp_channels = storage
+        .get_all_temporary_voice_channels()
+        .await
+        .unwrap_or_default();
+
+    for mut temp_channel in temp_channels {
+        if !guild_ids.contains(&temp_channel.guild_id) {
+            continue;
+        }
+
+        let delete_at = match temp_channel.pending_deletion_at {
+            None => continue,
+            Some(delete_at) => delete_at,
+        };
+
+        // Someone joined while the bot was offline
+        if get_member_count(ctx, temp_channel.guild_id, temp_channel.channel_id) != Some(0) {
+            cancel_deletion(storage.as_ref(), &mut temp_channel).await;
+            continue;
+        }
+
+        // A new generation so a task left over from an earlier reconcile does not delete it as well
+        temp_channel.pending_deletion_generation = temp_channel.pending_deletion_generation.wrapping_add(1);
+        storage.set_temporary_voice_channel(&temp_channel).await;
+
+        spawn_deletion(ctx, temp_channel.channel_id, delete_at, temp_channel.pending_deletion_generation);
+    }
+}
+
+pub(super) fn get_member_count(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) -> Option<usize> {
+    let guild = guild_id.to_guild_cached(ctx)?;
+
+    let count = guild
+        .voice_states
+        .values()
+        .filter(|voice_state| voice_state.channel_id == Some(channel_id))
+        .count();
+
+    Some(count)
+}
+
+fn spawn_deletion(ctx: &Context, channel_id: ChannelId, delete_at: u64, generation: u64) {
+    let ctx = ctx.clone();
+
+    tokio::spawn(async move {
+        let delay = delete_at.saturating_sub(get_current_time());
+        tokio::time::sleep(Duration::from_secs(delay)).await;
+
+        delete_if_still_pending(&ctx, channel_id, generation).await;
+    });
+}
+
+async fn delete_if_still_pending(ctx: &Context, channel_id: ChannelId, generation: u64) {
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    // The deletion was cancelled, or replaced by a later one, when the channel was joined in the meantime.
+    // Deletions scheduled within the same second share a timestamp, so the generation tells them apart
+    let mut temp_channel = match storage.get_temporary_voice_channel(&channel_id).await {
+        Some(temp_channel)
+            if temp_channel.pending_deletion_at.is_some()
+                && temp_channel.pending_deletion_generation == generation =>
+        {
+            temp_channel
+        }
+        _ => return,
+    };
+
+    if get_member_count(ctx, temp_channel.guild_id, channel_id) != Some(0) {
+        cancel_deletion(storage.as_ref(), &mut temp_channel).await;
+        return;
+    }
+
+    match channel_id.delete(ctx).await {
+        Ok(_) => remove_deleted_temporary_channel(ctx, &storage, &temp_channel).await,
+        // The channel was deleted while the bot was offline, so only the saved channel is left to clean up
+        Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(response)))
+            if response.error.code == UNKNOWN_CHANNEL_ERROR_CODE =>
+        {
+            remove_deleted_temporary_channel(ctx, &storage, &temp_channel).await;
+        }
+        Err(why) => println!("Could not delete temporary channel after the grace period: {:?}", why),
+    }
+}
+
+fn get_current_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
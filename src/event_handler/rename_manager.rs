@@ -0,0 +1,276 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serenity::all::{ChannelId, Context, EditChannel};
+
+use crate::event_handler::pending_deletion::get_member_count;
+use crate::guild_config::GuildConfig;
+use crate::room_words::get_room_rng;
+use crate::template::{get_name_from_template, Placeholder, Template, TemplateContext};
+use crate::temporary_channel::TemporaryVoiceChannel;
+use crate::StorageKey;
+
+/// Discord allows a channel to be renamed this many times per [`RENAME_WINDOW`]
+const MAX_RENAMES: usize = 2;
+
+const RENAME_WINDOW: Duration = Duration::from_secs(600);
+
+/// Waits this long after the last request before renaming, so switching games a few times in a row
+/// only renames the channel once
+const DEBOUNCE: Duration = Duration::from_secs(10);
+
+/// Renames temporary channels from their naming standard while keeping to Discord's rename limit.
+/// Requests for a channel that can not be renamed yet are queued and the latest name is used once it can
+#[derive(Clone)]
+pub struct RenameManager {
+    channels: Arc<Mutex<HashMap<ChannelId, RenameState>>>,
+}
+
+#[derive(Default)]
+struct RenameState {
+    /// When the channel was renamed within the last [`RENAME_WINDOW`], oldest first
+    renames: VecDeque<Instant>,
+    /// When the queued rename should happen, [`None`] when nothing is queued
+    due: Option<Instant>,
+    /// Whether a task is waiting to rename the channel
+    running: bool,
+}
+
+impl RenameState {
+    fn forget_old_renames(&mut self, now: Instant) {
+        while self
+            .renames
+            .front()
+            .is_some_and(|renamed_at| now.duration_since(*renamed_at) >= RENAME_WINDOW)
+        {
+            self.renames.pop_front();
+        }
+    }
+
+    /// The first moment the queued rename is both debounced and allowed by the rename limit
+    fn next_rename_at(&self) -> Option<Instant> {
+        let due = self.due?;
+
+        match self.renames.len() >= MAX_RENAMES {
+            false => Some(due),
+            true => {
+                let free_at = self.renames[self.renames.len() - MAX_RENAMES] + RENAME_WINDOW;
+                Some(due.max(free_at))
+            }
+        }
+    }
+}
+
+impl RenameManager {
+    pub fn new() -> Self {
+        RenameManager {
+            channels: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Queues the channel to be renamed from its naming standard once the debounce and rename limit allow it
+    pub fn request_rename(&self, ctx: &Context, channel_id: ChannelId) {
+        let now = Instant::now();
+        let mut channels = self.channels.lock().unwrap();
+
+        channels.retain(|_, state| {
+            state.forget_old_renames(now);
+            state.running || !state.renames.is_empty()
+        });
+
+        let state = channels.entry(channel_id).or_default();
+        state.due = Some(now + DEBOUNCE);
+
+        if state.running {
+            return;
+        }
+
+        state.running = true;
+
+        let rename_manager = self.clone();
+        let ctx = ctx.clone();
+
+        tokio::spawn(async move {
+            rename_manager.run_queue(&ctx, channel_id).await;
+        });
+    }
+
+    /// Queues a rename when the name of the channel shows how many members are in it
+    pub fn request_member_count_rename(&self, ctx: &Context, temp_channel: &TemporaryVoiceChannel) {
+        let shows_member_count = Template::parse(&temp_channel.template_name)
+            .is_ok_and(|template| template.uses(Placeholder::MemberCount));
+
+        if shows_member_count {
+            self.request_rename(ctx, temp_channel.channel_id);
+        }
+    }
+
+    async fn run_queue(&self, ctx: &Context, channel_id: ChannelId) {
+        loop {
+            let wait_until = {
+                let now = Instant::now();
+                let mut channels = self.channels.lock().unwrap();
+
+                let state = match channels.get_mut(&channel_id) {
+                    None => return,
+                    Some(state) => state,
+                };
+
+                state.forget_old_renames(now);
+
+                match state.next_rename_at() {
+                    None => {
+                        state.running = false;
+                        return;
+                    }
+                    Some(rename_at) if rename_at <= now => {
+                        state.due = None;
+                        None
+                    }
+                    Some(rename_at) => Some(rename_at),
+                }
+            };
+
+            if let Some(wait_until) = wait_until {
+                tokio::time::sleep_until(wait_until.into()).await;
+                continue;
+            }
+
+            if rename_temporary_channel(ctx, channel_id).await {
+                if let Some(state) = self.channels.lock().unwrap().get_mut(&channel_id) {
+                    state.renames.push_back(Instant::now());
+                }
+            }
+        }
+    }
+}
+
+/// Renders the naming standard of the temporary channel again and renames it when the name changed,
+/// returns whether it was renamed. Channels whose name differs from the last one the bot gave them are skipped
+async fn rename_temporary_channel(ctx: &Context, channel_id: ChannelId) -> bool {
+    let storage = {
+        let data_read = ctx.data.read().await;
+        match data_read.get::<StorageKey>().cloned() {
+            None => {
+                println!("Storage is null!");
+                panic!()
+            }
+            Some(storage) => storage,
+        }
+    };
+
+    let mut temp_channel = match storage.get_temporary_voice_channel(&channel_id).await {
+        None => return false,
+        Some(temp_channel) => temp_channel,
+    };
+
+    // A channel that was renamed by hand keeps the name it was given
+    let live_name = temp_channel
+        .guild_id
+        .to_guild_cached(ctx)
+        .and_then(|guild| guild.channels.get(&channel_id).map(|channel| channel.name.clone()));
+
+    if live_name.as_ref() != Some(&temp_channel.name) {
+        return false;
+    }
+
+    let guild_config = storage
+        .get_guild_config(&temp_channel.guild_id)
+        .await
+        .unwrap_or_else(|| GuildConfig::new(temp_channel.guild_id));
+
+    // Channels saved before `%room%` was stored pick it once and keep it from then on
+    if temp_channel.room.is_none() {
+        let owner_name = temp_channel.guild_id.to_guild_cached(ctx).and_then(|guild| {
+            let owner = guild.members.get(&temp_channel.owner_id)?;
+            Some(owner.display_name().to_string())
+        });
+
+        if let Some(owner_name) = owner_name {
+            let stable_room = storage
+                .get_creator_voice_config(&temp_channel.creator_id)
+                .await
+                .is_some_and(|config| config.stable_room);
+            let mut room_rng = get_room_rng(stable_room, temp_channel.owner_id, temp_channel.creator_id);
+
+            temp_channel.room = Some(guild_config.get_room(&owner_name, &mut room_rng));
+            storage.set_temporary_voice_channel(&temp_channel).await;
+        }
+    }
+
+    let name = match render_channel_name(ctx, &temp_channel, guild_config) {
+        None => return false,
+        Some(name) => name,
+    };
+
+    if name.trim().is_empty() || name == temp_channel.name {
+        return false;
+    }
+
+    let builder = EditChannel::new()
+        .name(&name)
+        .audit_log_reason("Temp voice bot");
+
+    if let Err(why) = channel_id.edit(ctx, builder).await {
+        println!("Could not rename temporary channel: {:?}", why);
+        return false;
+    }
+
+    if let Some(text_channel_id) = temp_channel.text_channel_id {
+        if let Err(why) = text_channel_id.edit(ctx, EditChannel::new().name(&name)).await {
+            println!("Could not rename companion text channel: {:?}", why);
+        }
+    }
+
+    temp_channel.name = name;
+    storage.set_temporary_voice_channel(&temp_channel).await;
+
+    true
+}
+
+/// The name the temporary channel would get from its naming standard right now,
+/// [`None`] when the owner or the channel are not in the cache
+fn render_channel_name(ctx: &Context, temp_channel: &TemporaryVoiceChannel, guild_config: GuildConfig) -> Option<String> {
+    let (owner, creator_name) = {
+        let guild = temp_channel.guild_id.to_guild_cached(ctx)?;
+        let owner = guild.members.get(&temp_channel.owner_id)?.clone();
+        let creator_name = guild.channels.get(&temp_channel.creator_id).map(|channel| channel.name.clone());
+        (owner, creator_name)
+    };
+
+    let mut template_context = TemplateContext::for_member(ctx, &owner, temp_channel.number);
+    template_context.creator_name = creator_name;
+    template_context.member_count = get_member_count(ctx, temp_channel.guild_id, temp_channel.channel_id);
+    template_context.now = Some(guild_config.get_current_time());
+    template_context.activity_priority = guild_config.activity_priority;
+    template_context.room = temp_channel.room.clone();
+
+    Some(get_name_from_template(&temp_channel.template_name, &template_context))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event_handler::rename_manager::{RenameState, DEBOUNCE, RENAME_WINDOW};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn check_rename_limit() {
+        let now = Instant::now();
+        let mut state = RenameState::default();
+        assert_eq!(state.next_rename_at(), None);
+
+        state.due = Some(now + DEBOUNCE);
+        assert_eq!(state.next_rename_at(), Some(now + DEBOUNCE));
+
+        state.renames.push_back(now - Duration::from_secs(120));
+        assert_eq!(state.next_rename_at(), Some(now + DEBOUNCE));
+
+        state.renames.push_back(now - Duration::from_secs(60));
+        assert_eq!(state.next_rename_at(), Some(now - Duration::from_secs(120) + RENAME_WINDOW));
+
+        state.forget_old_renames(now + RENAME_WINDOW - Duration::from_secs(90));
+        assert_eq!(state.renames.len(), 1);
+        assert_eq!(state.next_rename_at(), Some(now + DEBOUNCE));
+    }
+}
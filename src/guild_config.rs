@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serenity::all::GuildId;
+
+use crate::activity_priority::ActivityPriority;
+use crate::room_words::{get_room, RoomLocale, RoomWords};
+
+/// Settings that apply to every creator channel of a server
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GuildConfig {
+    pub(crate) guild_id: GuildId,
+    /// Name of the time zone `%date%` and `%time%` are shown in, like `Europe/Amsterdam`
+    #[serde(default)]
+    pub(crate) timezone: Option<String>,
+    /// Which activity `%current_activity%` shows when the owner has more than one
+    #[serde(default)]
+    pub(crate) activity_priority: ActivityPriority,
+    /// Language of the bundled words `%room%` is picked from
+    #[serde(default)]
+    pub(crate) room_locale: RoomLocale,
+    /// Words uploaded for this server, used before the words of [`Self::room_locale`]
+    #[serde(default)]
+    pub(crate) room_words: Option<RoomWords>,
+}
+
+impl GuildConfig {
+    pub fn new(guild_id: GuildId) -> Self {
+        Self {
+            guild_id,
+            timezone: None,
+            activity_priority: ActivityPriority::default(),
+            room_locale: RoomLocale::default(),
+            room_words: None,
+        }
+    }
+
+    /// The configured time zone, UTC when none is set or it is no longer known
+    pub(crate) fn get_timezone(&self) -> Tz {
+        self.timezone
+            .as_ref()
+            .and_then(|timezone| timezone.parse::<Tz>().ok())
+            .unwrap_or(Tz::UTC)
+    }
+
+    pub(crate) fn get_current_time(&self) -> DateTime<Tz> {
+        Utc::now().with_timezone(&self.get_timezone())
+    }
+
+    /// Picks a `%room%` for the member name from this server's words
+    pub(crate) fn get_room(&self, member_name: &str, rng: &mut impl Rng) -> String {
+        get_room(member_name, self.room_words.as_ref(), self.room_locale, rng)
+    }
+}
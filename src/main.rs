@@ -2,13 +2,20 @@ use std::env;
 use std::sync::Arc;
 
 use crate::storage::{SQLiteStorage, Storage};
-use serenity::all::{EventHandler, GatewayIntents};
+use serenity::all::GatewayIntents;
 use serenity::prelude::TypeMapKey;
 use serenity::Client;
 
+mod activity_priority;
 mod creator_channel;
 mod event_handler;
+mod guild_config;
+mod number_pool;
+mod permission_preset;
+mod position_strategy;
+mod room_words;
 mod storage;
+mod template;
 mod temporary_channel;
 
 pub(crate) struct StorageKey;
@@ -0,0 +1,23 @@
+use crate::creator_channel::ChannelNumbers;
+use serde::{Deserialize, Serialize};
+use serenity::all::GuildId;
+
+/// Numbers shared by every creator channel of a server that uses the same pool name, so channels
+/// created from different creator channels never get the same number
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NumberPool {
+    pub(crate) guild_id: GuildId,
+    pub(crate) name: String,
+    #[serde(flatten)]
+    pub(crate) numbers: ChannelNumbers,
+}
+
+impl NumberPool {
+    pub fn new(guild_id: GuildId, name: String) -> Self {
+        Self {
+            guild_id,
+            name,
+            numbers: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use serenity::model::Permissions;
+
+/// Permissions given to the owner of a temporary channel and to the users they invite
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PermissionPreset {
+    /// The owner can manage the channel, its permissions and move members
+    #[default]
+    FullOwner,
+    /// The owner can move members but can not edit the channel or its permissions
+    ModerateOwner,
+    /// The owner gets no permissions beyond what the creator channel gives everyone
+    NoOwnerPerms,
+}
+
+impl PermissionPreset {
+    pub(crate) const ALL: [PermissionPreset; 3] = [
+        PermissionPreset::FullOwner,
+        PermissionPreset::ModerateOwner,
+        PermissionPreset::NoOwnerPerms,
+    ];
+
+    /// The value used for this preset in command options
+    pub(crate) fn id(&self) -> &'static str {
+        match self {
+            PermissionPreset::FullOwner => "full_owner",
+            PermissionPreset::ModerateOwner => "moderate_owner",
+            PermissionPreset::NoOwnerPerms => "no_owner_perms",
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            PermissionPreset::FullOwner => "Full owner",
+            PermissionPreset::ModerateOwner => "Moderate owner",
+            PermissionPreset::NoOwnerPerms => "No owner perms",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<PermissionPreset> {
+        PermissionPreset::ALL
+            .into_iter()
+            .find(|preset| preset.id() == id)
+    }
+
+    /// Permissions allowed for the owner on their channel, [`None`] if the owner gets no overwrite
+    pub(crate) fn owner_permissions(&self) -> Option<Permissions> {
+        match self {
+            PermissionPreset::FullOwner => Some(
+                Permissions::MOVE_MEMBERS
+                    | Permissions::MANAGE_CHANNELS
+                    | Permissions::MANAGE_ROLES
+                    | Permissions::VIEW_CHANNEL
+                    | Permissions::CONNECT
+                    | Permissions::SPEAK
+                    | Permissions::PRIORITY_SPEAKER
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::READ_MESSAGE_HISTORY,
+            ),
+            PermissionPreset::ModerateOwner => Some(
+                Permissions::MOVE_MEMBERS
+                    | Permissions::VIEW_CHANNEL
+                    | Permissions::CONNECT
+                    | Permissions::SPEAK
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::READ_MESSAGE_HISTORY,
+            ),
+            PermissionPreset::NoOwnerPerms => None,
+        }
+    }
+
+    /// Permissions allowed for a user invited by the owner, [`None`] if the invite only sends a DM
+    pub(crate) fn invite_permissions(&self) -> Option<Permissions> {
+        match self {
+            PermissionPreset::FullOwner => Some(
+                Permissions::VIEW_CHANNEL
+                    | Permissions::MOVE_MEMBERS // This permission lets the invited user join even if the voice channel is full
+                    | Permissions::CONNECT
+                    | Permissions::SPEAK
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::READ_MESSAGE_HISTORY,
+            ),
+            PermissionPreset::ModerateOwner => Some(
+                Permissions::VIEW_CHANNEL
+                    | Permissions::CONNECT
+                    | Permissions::SPEAK
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::READ_MESSAGE_HISTORY,
+            ),
+            PermissionPreset::NoOwnerPerms => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::permission_preset::PermissionPreset;
+    use serenity::model::Permissions;
+
+    #[test]
+    fn check_full_owner() {
+        let owner = PermissionPreset::FullOwner.owner_permissions().unwrap();
+        assert!(owner.contains(Permissions::MANAGE_CHANNELS | Permissions::MANAGE_ROLES | Permissions::MOVE_MEMBERS));
+
+        let invite = PermissionPreset::FullOwner.invite_permissions().unwrap();
+        assert!(invite.contains(Permissions::CONNECT | Permissions::MOVE_MEMBERS));
+        assert!(!invite.intersects(Permissions::MANAGE_CHANNELS | Permissions::MANAGE_ROLES));
+    }
+
+    #[test]
+    fn check_moderate_owner() {
+        let owner = PermissionPreset::ModerateOwner.owner_permissions().unwrap();
+        assert!(owner.contains(Permissions::MOVE_MEMBERS | Permissions::CONNECT));
+        assert!(!owner.intersects(Permissions::MANAGE_CHANNELS | Permissions::MANAGE_ROLES));
+
+        let invite = PermissionPreset::ModerateOwner.invite_permissions().unwrap();
+        assert!(invite.contains(Permissions::CONNECT));
+        assert!(!invite.intersects(Permissions::MOVE_MEMBERS | Permissions::MANAGE_CHANNELS | Permissions::MANAGE_ROLES));
+    }
+
+    #[test]
+    fn check_no_owner_perms() {
+        assert_eq!(PermissionPreset::NoOwnerPerms.owner_permissions(), None);
+        assert_eq!(PermissionPreset::NoOwnerPerms.invite_permissions(), None);
+    }
+
+    #[test]
+    fn check_ids() {
+        for preset in PermissionPreset::ALL {
+            assert_eq!(PermissionPreset::from_id(preset.id()), Some(preset));
+        }
+        assert_eq!(PermissionPreset::from_id("owner"), None);
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use serenity::all::ChannelId;
+use std::num::NonZeroU16;
+
+/// Where the creator channel and its temporary channels are put in their category
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PositionStrategy {
+    /// The creator channel first with the temporary channels below it sorted by number
+    CreatorOnTop,
+    /// The temporary channels sorted by number with the creator channel below them
+    #[default]
+    CreatorAtBottom,
+    /// Positions are never changed so channels can be ordered by hand
+    Manual,
+}
+
+impl PositionStrategy {
+    pub(crate) const ALL: [PositionStrategy; 3] = [
+        PositionStrategy::CreatorOnTop,
+        PositionStrategy::CreatorAtBottom,
+        PositionStrategy::Manual,
+    ];
+
+    /// The value used for this strategy in command options
+    pub(crate) fn id(&self) -> &'static str {
+        match self {
+            PositionStrategy::CreatorOnTop => "creator_on_top",
+            PositionStrategy::CreatorAtBottom => "creator_at_bottom",
+            PositionStrategy::Manual => "manual",
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            PositionStrategy::CreatorOnTop => "Creator channel on top",
+            PositionStrategy::CreatorAtBottom => "Creator channel at the bottom",
+            PositionStrategy::Manual => "Don't touch positions",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<PositionStrategy> {
+        PositionStrategy::ALL
+            .into_iter()
+            .find(|strategy| strategy.id() == id)
+    }
+
+    /// The channels in the order they should be shown, [`None`] if positions should not be changed.
+    /// `creator_id` is only given when the creator channel is in the same category as the temporary channels
+    pub(crate) fn order_channels(
+        &self,
+        creator_id: Option<ChannelId>,
+        temporary_channels: &[(NonZeroU16, ChannelId)],
+    ) -> Option<Vec<ChannelId>> {
+        let mut temporary_channels = temporary_channels.to_vec();
+        temporary_channels.sort();
+
+        let temporary_channel_ids = temporary_channels.into_iter().map(|(_, channel_id)| channel_id);
+
+        match self {
+            PositionStrategy::CreatorOnTop => Some(creator_id.into_iter().chain(temporary_channel_ids).collect()),
+            PositionStrategy::CreatorAtBottom => Some(temporary_channel_ids.chain(creator_id).collect()),
+            PositionStrategy::Manual => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::position_strategy::PositionStrategy;
+    use serenity::all::ChannelId;
+    use std::num::NonZeroU16;
+
+    #[test]
+    fn check_channel_order() {
+        let creator = ChannelId::new(1);
+        let temporary_channels = [
+            (NonZeroU16::new(3).unwrap(), ChannelId::new(30)),
+            (NonZeroU16::new(1).unwrap(), ChannelId::new(10)),
+            (NonZeroU16::new(2).unwrap(), ChannelId::new(20)),
+        ];
+
+        assert_eq!(
+            PositionStrategy::CreatorOnTop.order_channels(Some(creator), &temporary_channels),
+            Some(vec![creator, ChannelId::new(10), ChannelId::new(20), ChannelId::new(30)])
+        );
+        assert_eq!(
+            PositionStrategy::CreatorAtBottom.order_channels(Some(creator), &temporary_channels),
+            Some(vec![ChannelId::new(10), ChannelId::new(20), ChannelId::new(30), creator])
+        );
+        assert_eq!(
+            PositionStrategy::CreatorAtBottom.order_channels(None, &temporary_channels),
+            Some(vec![ChannelId::new(10), ChannelId::new(20), ChannelId::new(30)])
+        );
+        assert_eq!(PositionStrategy::Manual.order_channels(Some(creator), &temporary_channels), None);
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use rand::{rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, UserId};
+
+use crate::temporary_channel::{fold_char, get_first_letter};
+
+/// Most words a custom word list can have
+const MAX_ROOM_WORDS: usize = 500;
+
+/// Longest word a custom word list can have
+const MAX_ROOM_WORD_LENGTH: usize = 32;
+
+static ENGLISH: LazyLock<RoomWords> = LazyLock::new(|| load_bundled(include_str!("../locales/rooms/en.json")));
+
+static GERMAN: LazyLock<RoomWords> = LazyLock::new(|| load_bundled(include_str!("../locales/rooms/de.json")));
+
+static SPANISH: LazyLock<RoomWords> = LazyLock::new(|| load_bundled(include_str!("../locales/rooms/es.json")));
+
+static FRENCH: LazyLock<RoomWords> = LazyLock::new(|| load_bundled(include_str!("../locales/rooms/fr.json")));
+
+/// The language `%room%` is picked in when a server has no word list of its own
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoomLocale {
+    #[default]
+    English,
+    German,
+    Spanish,
+    French,
+}
+
+impl RoomLocale {
+    pub(crate) const ALL: [RoomLocale; 4] = [
+        RoomLocale::English,
+        RoomLocale::German,
+        RoomLocale::Spanish,
+        RoomLocale::French,
+    ];
+
+    /// The value used for this locale in command options
+    pub(crate) fn id(&self) -> &'static str {
+        match self {
+            RoomLocale::English => "en",
+            RoomLocale::German => "de",
+            RoomLocale::Spanish => "es",
+            RoomLocale::French => "fr",
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            RoomLocale::English => "English",
+            RoomLocale::German => "Deutsch",
+            RoomLocale::Spanish => "Español",
+            RoomLocale::French => "Français",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Option<RoomLocale> {
+        RoomLocale::ALL
+            .into_iter()
+            .find(|locale| locale.id() == id)
+    }
+
+    /// The bundled word list
+    fn get_room_words(&self) -> &'static RoomWords {
+        match self {
+            RoomLocale::English => &ENGLISH,
+            RoomLocale::German => &GERMAN,
+            RoomLocale::Spanish => &SPANISH,
+            RoomLocale::French => &FRENCH,
+        }
+    }
+}
+
+/// Words `%room%` is picked from by the first letter of the owner's name, written as
+/// `{ "a": ["Atrium", "Arena"], "fallback": ["Room"] }` in JSON or `a = ["Atrium", "Arena"]` in TOML
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoomWords {
+    /// Used for names that start with a letter that has no words
+    #[serde(default)]
+    pub(crate) fallback: Vec<String>,
+    /// The words for each lowercase letter
+    #[serde(flatten)]
+    pub(crate) words: BTreeMap<String, Vec<String>>,
+}
+
+impl RoomWords {
+    pub(crate) fn parse_json(source: &str) -> Result<RoomWords, String> {
+        let room_words: RoomWords = serde_json::from_str(source).map_err(|why| format!("The word list is not valid JSON: {}", why))?;
+        room_words.validate()
+    }
+
+    pub(crate) fn parse_toml(source: &str) -> Result<RoomWords, String> {
+        let room_words: RoomWords = toml::from_str(source).map_err(|why| format!("The word list is not valid TOML: {}", why))?;
+        room_words.validate()
+    }
+
+    /// Checks every key is a single letter and the words fit in a channel name, keys are folded the
+    /// same way as the first letter of names so `É` and `e` share their words
+    fn validate(self) -> Result<RoomWords, String> {
+        let mut words: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for (key, key_words) in self.words {
+            let mut chars = key.chars();
+
+            let letter = match (chars.next(), chars.next()) {
+                (Some(letter), None) => fold_char(letter),
+                _ => None,
+            };
+
+            let letter = match letter {
+                Some(letter) => letter,
+                None => return Err(format!("`{}` is not a single letter, use `fallback` for names that start with anything else!", key)),
+            };
+
+            words.entry(letter.to_string()).or_default().extend(key_words);
+        }
+
+        let room_words = RoomWords { fallback: self.fallback, words };
+
+        let all_words = room_words.fallback.iter().chain(room_words.words.values().flatten());
+        let mut word_count = 0;
+
+        for word in all_words {
+            if word.trim().is_empty() || word.chars().count() > MAX_ROOM_WORD_LENGTH {
+                return Err(format!("Every word must be between 1 and {} characters, `{}` is not!", MAX_ROOM_WORD_LENGTH, word));
+            }
+
+            word_count += 1;
+        }
+
+        if word_count == 0 {
+            return Err("The word list does not have any words!".to_string());
+        }
+
+        if word_count > MAX_ROOM_WORDS {
+            return Err(format!("A word list can have at most {} words!", MAX_ROOM_WORDS));
+        }
+
+        Ok(room_words)
+    }
+
+    /// The words for names starting with the letter, the fallback words when the letter has none
+    /// and [`None`] when there are no fallback words either
+    fn get_words(&self, first_letter: Option<char>) -> Option<Vec<&str>> {
+        let words = first_letter
+            .and_then(|first_letter| self.words.get(&first_letter.to_string()))
+            .filter(|words| !words.is_empty())
+            .unwrap_or(&self.fallback);
+
+        match words.is_empty() {
+            true => None,
+            false => Some(words.iter().map(String::as_str).collect()),
+        }
+    }
+
+    pub(crate) fn word_count(&self) -> usize {
+        self.fallback.len() + self.words.values().map(Vec::len).sum::<usize>()
+    }
+}
+
+fn load_bundled(source: &str) -> RoomWords {
+    RoomWords::parse_json(source).expect("Bundled word lists should always be valid")
+}
+
+/// The random number generator `%room%` is picked with. A stable room is seeded by the member and
+/// creator channel so the member gets the same room from that creator channel every time. ChaCha8
+/// is used because its output is fixed, unlike [`rand::rngs::StdRng`] which may change between
+/// releases of rand and give members a different room after an update
+pub(crate) fn get_room_rng(stable_room: bool, user_id: UserId, creator_id: ChannelId) -> ChaCha8Rng {
+    match stable_room {
+        true => {
+            let mut seed = [0; 32];
+            seed[..8].copy_from_slice(&user_id.get().to_le_bytes());
+            seed[8..16].copy_from_slice(&creator_id.get().to_le_bytes());
+            ChaCha8Rng::from_seed(seed)
+        }
+        false => ChaCha8Rng::from_seed(rng().random()),
+    }
+}
+
+/// Picks a `%room%` for the member name from the custom word list, then the locale's word list and
+/// lastly the English words
+pub(crate) fn get_room(
+    member_name: &str,
+    room_words: Option<&RoomWords>,
+    room_locale: RoomLocale,
+    rng: &mut impl Rng,
+) -> String {
+    let first_letter = get_first_letter(member_name);
+
+    let words = room_words
+        .and_then(|room_words| room_words.get_words(first_letter))
+        .or_else(|| room_locale.get_room_words().get_words(first_letter))
+        .or_else(|| ENGLISH.get_words(first_letter))
+        .expect("The English word list should always have fallback words");
+
+    words[rng.random_range(0..words.len())].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::room_words::{get_room, get_room_rng, RoomLocale, RoomWords};
+    use rand::{rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use serenity::all::{ChannelId, UserId};
+
+    #[test]
+    fn check_room_words() {
+        for locale in RoomLocale::ALL {
+            assert_eq!(RoomLocale::from_id(locale.id()), Some(locale));
+            assert!(!get_room("Inbound", None, locale, &mut rng()).is_empty());
+        }
+
+        assert_eq!(get_room("Ümit", None, RoomLocale::English, &mut rng()), "University");
+        assert_eq!(get_room("🎮", None, RoomLocale::English, &mut rng()), "VC");
+        assert!(["Institute", "Inn", "Island"].contains(&get_room("𝓘", None, RoomLocale::English, &mut rng()).as_str()));
+        assert_eq!(get_room("Дмитрий", None, RoomLocale::English, &mut rng()), "VC");
+
+        let german = ["Insel", "Institut"];
+        assert!(german.contains(&get_room("Inbound", None, RoomLocale::German, &mut rng()).as_str()));
+        assert_eq!(get_room("Xaver", None, RoomLocale::German, &mut rng()), "Raum");
+        assert_eq!(get_room("Úrsula", None, RoomLocale::Spanish, &mut rng()), "Universidad");
+
+        let json = RoomWords::parse_json(r#"{ "I": ["Igloo"], "fallback": ["Hangout"] }"#).unwrap();
+        let toml = RoomWords::parse_toml("i = [\"Igloo\"]\nfallback = [\"Hangout\"]").unwrap();
+        assert_eq!(json, toml);
+        assert_eq!(json.word_count(), 2);
+
+        assert_eq!(get_room("Inbound", Some(&json), RoomLocale::German, &mut rng()), "Igloo");
+        assert_eq!(get_room("Rotten", Some(&json), RoomLocale::German, &mut rng()), "Hangout");
+
+        let without_fallback = RoomWords::parse_json(r#"{ "i": ["Igloo"] }"#).unwrap();
+        assert_eq!(get_room("Xaver", Some(&without_fallback), RoomLocale::German, &mut rng()), "Raum");
+        assert_eq!(get_room("🎮", Some(&without_fallback), RoomLocale::English, &mut rng()), "VC");
+
+        let stable_room = |user_id: u64, creator_id: u64| {
+            get_room("Inbound", None, RoomLocale::English, &mut get_room_rng(true, UserId::new(user_id), ChannelId::new(creator_id)))
+        };
+        assert!((0..10).all(|_| stable_room(1, 2) == stable_room(1, 2)));
+        assert!((1..=20).any(|user_id| stable_room(user_id, 2) != stable_room(1, 2)));
+        // A stable room must not change between releases
+        assert_eq!(stable_room(1, 2), "Institute");
+        assert_eq!(stable_room(2, 2), "Inn");
+        assert_eq!(stable_room(987654321987654321, 123456789123456789), "Island");
+
+        let mut seeded = ChaCha8Rng::seed_from_u64(7);
+        let rooms: Vec<String> = (0..5).map(|_| get_room("Inbound", None, RoomLocale::English, &mut seeded)).collect();
+        let mut seeded = ChaCha8Rng::seed_from_u64(7);
+        assert!(rooms.iter().all(|room| *room == get_room("Inbound", None, RoomLocale::English, &mut seeded)));
+
+        assert!(RoomWords::parse_json(r#"{ "ab": ["Abbey"] }"#).is_err());
+        assert!(RoomWords::parse_json(r#"{ "a": [""] }"#).is_err());
+        assert!(RoomWords::parse_json(r#"{ "a": [] }"#).is_err());
+        assert!(RoomWords::parse_json("not json").is_err());
+    }
+}
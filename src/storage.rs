@@ -1,9 +1,10 @@
 use crate::creator_channel::CreatorChannelConfig;
+use crate::guild_config::GuildConfig;
+use crate::number_pool::NumberPool;
 use crate::temporary_channel::TemporaryVoiceChannel;
 use async_trait::async_trait;
 use rusqlite::{params, Connection};
-use serde_json;
-use serenity::all::ChannelId;
+use serenity::all::{ChannelId, GuildId, UserId};
 use tokio::task;
 
 #[async_trait]
@@ -14,6 +15,7 @@ pub trait Storage: Send + Sync {
     ) -> Option<CreatorChannelConfig>;
     async fn set_creator_voice_config(&self, creator_config: &CreatorChannelConfig);
     async fn delete_creator_voice_config(&self, channel_id: &ChannelId);
+    async fn get_creator_voice_configs(&self, guild_id: &GuildId) -> Option<Vec<CreatorChannelConfig>>;
 
     async fn get_temporary_voice_channel(
         &self,
@@ -22,6 +24,18 @@ pub trait Storage: Send + Sync {
     async fn set_temporary_voice_channel(&self, temporary_channel: &TemporaryVoiceChannel);
     async fn delete_temporary_voice_channel(&self, channel_id: &ChannelId);
     async fn get_all_temporary_voice_channels(&self) -> Option<Vec<TemporaryVoiceChannel>>;
+    async fn get_creator_temporary_voice_channels(&self, creator_id: &ChannelId) -> Option<Vec<TemporaryVoiceChannel>>;
+    async fn get_owned_temporary_voice_channels(
+        &self,
+        guild_id: &GuildId,
+        owner_id: &UserId,
+    ) -> Option<Vec<TemporaryVoiceChannel>>;
+
+    async fn get_number_pool(&self, guild_id: &GuildId, name: &str) -> Option<NumberPool>;
+    async fn set_number_pool(&self, number_pool: &NumberPool);
+
+    async fn get_guild_config(&self, guild_id: &GuildId) -> Option<GuildConfig>;
+    async fn set_guild_config(&self, guild_config: &GuildConfig);
 }
 
 pub struct SQLiteStorage {
@@ -46,10 +60,35 @@ impl SQLiteStorage {
                 config_data TEXT NOT NULL
             );
 
+            CREATE INDEX IF NOT EXISTS creator_channel_config_guild ON creator_channel_config (
+                json_extract(config_data, '$.guild_id')
+            );
+
             CREATE TABLE IF NOT EXISTS temporary_voice_channel (
                 channel_id INTEGER PRIMARY KEY,
                 config_data TEXT NOT NULL
             );
+
+            CREATE INDEX IF NOT EXISTS temporary_voice_channel_creator ON temporary_voice_channel (
+                json_extract(config_data, '$.creator_id')
+            );
+
+            CREATE INDEX IF NOT EXISTS temporary_voice_channel_owner ON temporary_voice_channel (
+                json_extract(config_data, '$.guild_id'),
+                json_extract(config_data, '$.owner_id')
+            );
+
+            CREATE TABLE IF NOT EXISTS number_pool (
+                guild_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                pool_data TEXT NOT NULL,
+                PRIMARY KEY (guild_id, name)
+            );
+
+            CREATE TABLE IF NOT EXISTS guild_config (
+                guild_id INTEGER PRIMARY KEY,
+                config_data TEXT NOT NULL
+            );
             ",
         )?;
         Ok(())
@@ -116,6 +155,40 @@ impl Storage for SQLiteStorage {
         .expect("Failed to delete creator voice channel");
     }
 
+    async fn get_creator_voice_configs(&self, guild_id: &GuildId) -> Option<Vec<CreatorChannelConfig>> {
+        let db_path = self.database_path.clone();
+        // Ids are stored as strings in the JSON
+        let guild_id = guild_id.to_string();
+        task::spawn_blocking(move || {
+            let conn = Connection::open(db_path).ok()?;
+
+            let mut statement = conn
+                .prepare(
+                    "
+                    SELECT config_data FROM creator_channel_config
+                    WHERE json_extract(config_data, '$.guild_id') = ?1
+                    ",
+                )
+                .ok()?;
+
+            let rows = statement
+                .query_map(params![guild_id], |row| {
+                    let config_data: String = row.get(0)?;
+                    let config: CreatorChannelConfig = serde_json::from_str(&config_data)
+                        .map_err(|_| rusqlite::Error::InvalidQuery)?;
+                    Ok(config)
+                })
+                .ok()?;
+
+            let configs: Vec<CreatorChannelConfig> =
+                rows.filter_map(|result| result.ok()).collect();
+
+            Some(configs)
+        })
+        .await
+        .unwrap_or(None)
+    }
+
     async fn get_temporary_voice_channel(
         &self,
         channel_id: &ChannelId,
@@ -201,4 +274,247 @@ impl Storage for SQLiteStorage {
         .await
         .unwrap_or(None)
     }
+
+    async fn get_creator_temporary_voice_channels(&self, creator_id: &ChannelId) -> Option<Vec<TemporaryVoiceChannel>> {
+        let db_path = self.database_path.clone();
+        // Ids are stored as strings in the JSON
+        let creator_id = creator_id.to_string();
+        task::spawn_blocking(move || {
+            let conn = Connection::open(db_path).ok()?;
+
+            let mut statement = conn
+                .prepare(
+                    "
+                    SELECT config_data FROM temporary_voice_channel
+                    WHERE json_extract(config_data, '$.creator_id') = ?1
+                    ",
+                )
+                .ok()?;
+
+            let rows = statement
+                .query_map(params![creator_id], |row| {
+                    let config_data: String = row.get(0)?;
+                    let temp_channel: TemporaryVoiceChannel = serde_json::from_str(&config_data)
+                        .map_err(|_| rusqlite::Error::InvalidQuery)?;
+                    Ok(temp_channel)
+                })
+                .ok()?;
+
+            let temp_channels: Vec<TemporaryVoiceChannel> =
+                rows.filter_map(|result| result.ok()).collect();
+
+            Some(temp_channels)
+        })
+        .await
+        .unwrap_or(None)
+    }
+
+    async fn get_owned_temporary_voice_channels(
+        &self,
+        guild_id: &GuildId,
+        owner_id: &UserId,
+    ) -> Option<Vec<TemporaryVoiceChannel>> {
+        let db_path = self.database_path.clone();
+        // Ids are stored as strings in the JSON
+        let guild_id = guild_id.to_string();
+        let owner_id = owner_id.to_string();
+        task::spawn_blocking(move || {
+            let conn = Connection::open(db_path).ok()?;
+
+            let mut statement = conn
+                .prepare(
+                    "
+                    SELECT config_data FROM temporary_voice_channel
+                    WHERE json_extract(config_data, '$.guild_id') = ?1
+                    AND json_extract(config_data, '$.owner_id') = ?2
+                    ",
+                )
+                .ok()?;
+
+            let rows = statement
+                .query_map(params![guild_id, owner_id], |row| {
+                    let config_data: String = row.get(0)?;
+                    let temp_channel: TemporaryVoiceChannel = serde_json::from_str(&config_data)
+                        .map_err(|_| rusqlite::Error::InvalidQuery)?;
+                    Ok(temp_channel)
+                })
+                .ok()?;
+
+            let temp_channels: Vec<TemporaryVoiceChannel> =
+                rows.filter_map(|result| result.ok()).collect();
+
+            Some(temp_channels)
+        })
+        .await
+        .unwrap_or(None)
+    }
+
+    async fn get_number_pool(&self, guild_id: &GuildId, name: &str) -> Option<NumberPool> {
+        let db_path = self.database_path.clone();
+        let guild_id_u64 = guild_id.get();
+        let name = name.to_string();
+        task::spawn_blocking(move || {
+            let conn = Connection::open(db_path).ok()?;
+            let mut stmt = conn
+                .prepare("SELECT pool_data FROM number_pool WHERE guild_id = ?1 AND name = ?2")
+                .ok()?;
+            let mut rows = stmt.query(params![guild_id_u64, name]).ok()?;
+            if let Some(row) = rows.next().ok()? {
+                let pool_data: String = row.get(0).ok()?;
+                let number_pool: NumberPool = serde_json::from_str(&pool_data).ok()?;
+                Some(number_pool)
+            } else {
+                None
+            }
+        })
+        .await
+        .unwrap_or(None)
+    }
+
+    async fn set_number_pool(&self, number_pool: &NumberPool) {
+        let db_path = self.database_path.clone();
+        let guild_id_u64 = number_pool.guild_id.get();
+        let name = number_pool.name.clone();
+        let pool_data = serde_json::to_string(&number_pool).unwrap_or_default();
+        task::spawn_blocking(move || {
+            let conn = Connection::open(db_path).ok()?;
+            conn.execute(
+                "
+                INSERT INTO number_pool (guild_id, name, pool_data) VALUES (?1, ?2, ?3)
+                ON CONFLICT(guild_id, name) DO UPDATE SET pool_data=excluded.pool_data
+                ",
+                params![guild_id_u64, name, pool_data],
+            )
+            .ok()
+        })
+        .await
+        .expect("Failed to save number pool");
+    }
+
+    async fn get_guild_config(&self, guild_id: &GuildId) -> Option<GuildConfig> {
+        let db_path = self.database_path.clone();
+        let guild_id_u64 = guild_id.get();
+        task::spawn_blocking(move || {
+            let conn = Connection::open(db_path).ok()?;
+            let mut stmt = conn
+                .prepare("SELECT config_data FROM guild_config WHERE guild_id = ?1")
+                .ok()?;
+            let mut rows = stmt.query(params![guild_id_u64]).ok()?;
+            if let Some(row) = rows.next().ok()? {
+                let config_data: String = row.get(0).ok()?;
+                let guild_config: GuildConfig = serde_json::from_str(&config_data).ok()?;
+                Some(guild_config)
+            } else {
+                None
+            }
+        })
+        .await
+        .unwrap_or(None)
+    }
+
+    async fn set_guild_config(&self, guild_config: &GuildConfig) {
+        let db_path = self.database_path.clone();
+        let guild_id_u64 = guild_config.guild_id.get();
+        let config_data = serde_json::to_string(&guild_config).unwrap_or_default();
+        task::spawn_blocking(move || {
+            let conn = Connection::open(db_path).ok()?;
+            conn.execute(
+                "
+                INSERT INTO guild_config (guild_id, config_data) VALUES (?1, ?2)
+                ON CONFLICT(guild_id) DO UPDATE SET config_data=excluded.config_data
+                ",
+                params![guild_id_u64, config_data],
+            )
+            .ok()
+        })
+        .await
+        .expect("Failed to save guild config");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::creator_channel::CreatorChannelConfig;
+    use crate::storage::{SQLiteStorage, Storage};
+    use crate::temporary_channel::TemporaryVoiceChannel;
+    use serenity::all::{ChannelId, GuildId, UserId};
+    use std::num::NonZeroU16;
+    use std::path::PathBuf;
+
+    fn create_database_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn create_config(guild_id: u64, creator_id: u64) -> CreatorChannelConfig {
+        serde_json::from_value(serde_json::json!({
+            "guild_id": guild_id.to_string(),
+            "creator_id": creator_id.to_string(),
+            "category_id": "1",
+            "naming_standard": "%name%",
+            "channel_numbers": [],
+            "user_limit": 0,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn check_creator_voice_configs_of_guild() {
+        let path = create_database_path("creator-voice-configs");
+        let storage = SQLiteStorage::new(path.to_str().unwrap()).unwrap();
+
+        storage.set_creator_voice_config(&create_config(1, 10)).await;
+        storage.set_creator_voice_config(&create_config(1, 11)).await;
+        storage.set_creator_voice_config(&create_config(2, 20)).await;
+
+        let mut creator_ids: Vec<ChannelId> = storage
+            .get_creator_voice_configs(&GuildId::new(1))
+            .await
+            .unwrap()
+            .iter()
+            .map(|config| config.creator_id)
+            .collect();
+        creator_ids.sort();
+        assert_eq!(creator_ids, vec![ChannelId::new(10), ChannelId::new(11)]);
+
+        let configs = storage.get_creator_voice_configs(&GuildId::new(3)).await.unwrap();
+        assert!(configs.is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn check_temporary_voice_channels_of_creator() {
+        let path = create_database_path("creator-temporary-voice-channels");
+        let storage = SQLiteStorage::new(path.to_str().unwrap()).unwrap();
+
+        for (channel_id, creator_id) in [(100, 10), (101, 10), (200, 20)] {
+            let temp_channel = TemporaryVoiceChannel::new(
+                GuildId::new(1),
+                ChannelId::new(channel_id),
+                ChannelId::new(creator_id),
+                UserId::new(1),
+                "Channel".to_string(),
+                "Channel".to_string(),
+                NonZeroU16::new(1).unwrap(),
+            );
+            storage.set_temporary_voice_channel(&temp_channel).await;
+        }
+
+        let mut channel_ids: Vec<ChannelId> = storage
+            .get_creator_temporary_voice_channels(&ChannelId::new(10))
+            .await
+            .unwrap()
+            .iter()
+            .map(|temp_channel| temp_channel.channel_id)
+            .collect();
+        channel_ids.sort();
+        assert_eq!(channel_ids, vec![ChannelId::new(100), ChannelId::new(101)]);
+
+        let temp_channels = storage.get_creator_temporary_voice_channels(&ChannelId::new(30)).await.unwrap();
+        assert!(temp_channels.is_empty());
+
+        std::fs::remove_file(path).unwrap();
+    }
 }
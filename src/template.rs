@@ -0,0 +1,931 @@
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroU16;
+
+use chrono::DateTime;
+use chrono_tz::Tz;
+use rand::rng;
+use serenity::all::{Activity, Context, Member, Presence};
+
+use crate::activity_priority::{get_activity_name, ActivityKind, ActivityPriority};
+use crate::room_words::{get_room, RoomLocale};
+use crate::temporary_channel::get_user_presence;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+const TIME_FORMAT: &str = "%H:%M";
+
+/// Characters that have a meaning in a naming standard and are written as text by putting `\` in front
+const ESCAPABLE: [char; 5] = ['\\', '%', '{', '}', '|'];
+
+/// Characters that have to be escaped in text
+const TEXT_SPECIAL: [char; 4] = ['\\', '%', '{', '}'];
+
+/// Characters that have to be escaped in filter arguments
+const ARGUMENT_SPECIAL: [char; 3] = ['\\', '%', '|'];
+
+/// Longest length the `truncate` filter accepts, channel names can not be longer anyway
+const MAX_TRUNCATE_LENGTH: usize = 100;
+
+/// Most digits the `pad` filter accepts
+const MAX_PAD_WIDTH: usize = 10;
+
+/// A value that is filled in when a channel name is rendered, written as `%name%` in a naming standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placeholder {
+    Number,
+    Name,
+    Room,
+    CurrentActivity,
+    Username,
+    Nickname,
+    Creator,
+    MemberCount,
+    Date,
+    Time,
+    HighestRole,
+    ActivityDetails,
+    ActivityType,
+}
+
+impl Placeholder {
+    pub(crate) const ALL: [Placeholder; 13] = [
+        Placeholder::Number,
+        Placeholder::Name,
+        Placeholder::Room,
+        Placeholder::CurrentActivity,
+        Placeholder::Username,
+        Placeholder::Nickname,
+        Placeholder::Creator,
+        Placeholder::MemberCount,
+        Placeholder::Date,
+        Placeholder::Time,
+        Placeholder::HighestRole,
+        Placeholder::ActivityDetails,
+        Placeholder::ActivityType,
+    ];
+
+    /// The name between the `%` signs
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Placeholder::Number => "number",
+            Placeholder::Name => "name",
+            Placeholder::Room => "room",
+            Placeholder::CurrentActivity => "current_activity",
+            Placeholder::Username => "username",
+            Placeholder::Nickname => "nickname",
+            Placeholder::Creator => "creator",
+            Placeholder::MemberCount => "member_count",
+            Placeholder::Date => "date",
+            Placeholder::Time => "time",
+            Placeholder::HighestRole => "highest_role",
+            Placeholder::ActivityDetails => "activity_details",
+            Placeholder::ActivityType => "activity_type",
+        }
+    }
+
+    pub(crate) fn description(&self) -> &'static str {
+        match self {
+            Placeholder::Number => "The number of the channel",
+            Placeholder::Name => "The display name of the owner",
+            Placeholder::Room => "A room name starting with the same letter as the owner's name",
+            Placeholder::CurrentActivity => "What the owner is playing, streaming or listening to, or \"No Game\"",
+            Placeholder::Username => "The username of the owner, the same in every server",
+            Placeholder::Nickname => "The server nickname of the owner, empty without one",
+            Placeholder::Creator => "The name of the creator channel",
+            Placeholder::MemberCount => "How many members are in the channel",
+            Placeholder::Date => "The date the channel was named on in the server's time zone, like 2024-12-31",
+            Placeholder::Time => "The time the channel was named at in the server's time zone, like 21:30",
+            Placeholder::HighestRole => "The name of the owner's highest role",
+            Placeholder::ActivityDetails => "What the owner is doing in their game, like the map or mode",
+            Placeholder::ActivityType => "What kind of activity %current_activity% is, like Playing or Listening",
+        }
+    }
+
+    /// How the placeholder is written in a naming standard
+    pub(crate) fn token(&self) -> String {
+        format!("%{}%", self.name())
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Placeholder> {
+        Placeholder::ALL
+            .into_iter()
+            .find(|placeholder| placeholder.name() == name)
+    }
+
+    /// Whether the placeholder is always filled in with a number
+    fn is_numeric(&self) -> bool {
+        matches!(self, Placeholder::Number | Placeholder::MemberCount)
+    }
+
+    /// Used when the placeholder has no value and no `default` filter gave it one
+    fn fallback(&self) -> &'static str {
+        match self {
+            Placeholder::CurrentActivity => "No Game",
+            _ => "",
+        }
+    }
+}
+
+/// Changes the value of a placeholder, written after the placeholder name like `%name|upper%`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    Upper,
+    Lower,
+    /// Keeps at most this many characters
+    Truncate(usize),
+    /// Used when the placeholder has no value
+    Default(String),
+    /// Puts zeros in front of a number until it has this many digits
+    Pad(usize),
+    /// Writes a number in roman numerals
+    Roman,
+}
+
+impl Filter {
+    pub(crate) const HELP: [(&'static str, &'static str); 6] = [
+        ("upper", "Makes the value uppercase"),
+        ("lower", "Makes the value lowercase"),
+        ("truncate:12", "Keeps at most 12 characters"),
+        ("default:Chilling", "Uses `Chilling` when there is no value"),
+        ("pad:2", "Puts zeros in front of a number until it has 2 digits"),
+        ("roman", "Writes a number in roman numerals"),
+    ];
+
+    fn parse(source: &str, placeholder: Placeholder) -> Result<Filter, TemplateError> {
+        let (name, argument) = match source.split_once(':') {
+            None => (source, None),
+            Some((name, argument)) => (name, Some(argument)),
+        };
+
+        let invalid = |reason: &'static str| TemplateError::InvalidFilterArgument(source.to_string(), reason);
+
+        let filter = match (name, argument) {
+            ("upper", None) => Filter::Upper,
+            ("lower", None) => Filter::Lower,
+            ("roman", None) => Filter::Roman,
+            ("truncate", argument) => match argument.and_then(|argument| argument.parse::<usize>().ok()) {
+                Some(length) if (1..=MAX_TRUNCATE_LENGTH).contains(&length) => Filter::Truncate(length),
+                _ => return Err(invalid("truncate needs a length between 1 and 100, like `truncate:12`")),
+            },
+            ("pad", argument) => match argument.and_then(|argument| argument.parse::<usize>().ok()) {
+                Some(width) if (1..=MAX_PAD_WIDTH).contains(&width) => Filter::Pad(width),
+                _ => return Err(invalid("pad needs a number of digits between 1 and 10, like `pad:2`")),
+            },
+            ("default", Some(argument)) => Filter::Default(argument.to_string()),
+            ("default", None) => return Err(invalid("default needs a value, like `default:Chilling`")),
+            ("upper" | "lower" | "roman", Some(_)) => return Err(invalid("this filter does not take a value")),
+            _ => return Err(TemplateError::UnknownFilter(name.to_string())),
+        };
+
+        if matches!(filter, Filter::Pad(_) | Filter::Roman) && !placeholder.is_numeric() {
+            return Err(TemplateError::UnsupportedFilter(name.to_string(), placeholder));
+        }
+
+        Ok(filter)
+    }
+
+    fn apply(&self, value: Option<String>) -> Option<String> {
+        match self {
+            Filter::Default(default) => match value {
+                Some(value) if !value.is_empty() => Some(value),
+                _ => Some(default.clone()),
+            },
+            Filter::Upper => value.map(|value| value.to_uppercase()),
+            Filter::Lower => value.map(|value| value.to_lowercase()),
+            Filter::Truncate(length) => value.map(|value| value.chars().take(*length).collect()),
+            Filter::Pad(width) => value.map(|value| format!("{:0>width$}", value, width = width)),
+            Filter::Roman => value.map(|value| match value.parse::<u32>() {
+                Ok(number) if number > 0 => to_roman(number),
+                _ => value,
+            }),
+        }
+    }
+}
+
+/// A placeholder with the filters that are applied to its value in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression {
+    placeholder: Placeholder,
+    filters: Vec<Filter>,
+}
+
+impl Expression {
+    fn parse(body: &str) -> Result<Expression, TemplateError> {
+        let mut parts = split_unescaped(body, '|').into_iter();
+
+        let name = parts.next().unwrap_or_default();
+        let placeholder = Placeholder::from_name(&name).ok_or(TemplateError::UnknownPlaceholder(name))?;
+
+        let filters = parts
+            .map(|part| Filter::parse(&part, placeholder))
+            .collect::<Result<Vec<Filter>, TemplateError>>()?;
+
+        Ok(Expression { placeholder, filters })
+    }
+
+    /// The filtered value, [`None`] when there is nothing to fill in
+    fn evaluate(&self, context: &TemplateContext) -> Option<String> {
+        self.apply_filters(context.resolve(self.placeholder))
+    }
+
+    /// The filtered value, or the filtered fallback of the placeholder when there is nothing to fill in
+    fn render(&self, context: &TemplateContext) -> String {
+        self.evaluate(context)
+            .or_else(|| self.apply_filters(Some(self.placeholder.fallback().to_string())))
+            .unwrap_or_default()
+    }
+
+    fn apply_filters(&self, value: Option<String>) -> Option<String> {
+        self.filters.iter().fold(value, |value, filter| filter.apply(value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Text(String),
+    Expression(Expression),
+    /// Left out when any placeholder in it has no value, written as `{? ... }`
+    Conditional(Vec<Segment>),
+}
+
+/// A parsed naming standard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A word wrapped in `%` that is not a [`Placeholder`]
+    UnknownPlaceholder(String),
+    UnknownFilter(String),
+    /// The filter as it was written and what it expects
+    InvalidFilterArgument(String, &'static str),
+    /// The filter can not be used on the placeholder
+    UnsupportedFilter(String, Placeholder),
+    /// A `{?` without the `}` that ends it
+    UnclosedConditional,
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::UnknownPlaceholder(placeholder) => write!(
+                f,
+                "`%{}%` is not a known placeholder! Available placeholders are:\n{}",
+                placeholder,
+                get_placeholder_help()
+            ),
+            TemplateError::UnknownFilter(filter) => write!(
+                f,
+                "`|{}` is not a known filter! Available filters are:\n{}",
+                filter,
+                get_filter_help()
+            ),
+            TemplateError::InvalidFilterArgument(filter, reason) => {
+                write!(f, "`|{}` is not valid, {}!", filter, reason)
+            }
+            TemplateError::UnclosedConditional => {
+                write!(f, "A conditional segment started with `{{?` is missing its closing `}}`!")
+            }
+            TemplateError::UnsupportedFilter(filter, placeholder) => write!(
+                f,
+                "`|{}` only works on numbers and can not be used on `{}`!",
+                filter,
+                placeholder.token()
+            ),
+        }
+    }
+}
+
+/// Everything a placeholder can be filled in with
+pub struct TemplateContext<'a> {
+    pub(crate) number: NonZeroU16,
+    pub(crate) user_name: &'a str,
+    pub(crate) presence: Option<Presence>,
+    pub(crate) username: Option<String>,
+    pub(crate) nickname: Option<String>,
+    pub(crate) creator_name: Option<String>,
+    pub(crate) member_count: Option<usize>,
+    /// The current time in the server's time zone
+    pub(crate) now: Option<DateTime<Tz>>,
+    pub(crate) highest_role: Option<String>,
+    /// Picks the activity the activity placeholders are filled in with
+    pub(crate) activity_priority: ActivityPriority,
+    /// Used for `%room%` instead of picking a new one
+    pub(crate) room: Option<String>,
+}
+
+impl<'a> TemplateContext<'a> {
+    pub fn new(number: NonZeroU16, user_name: &'a str) -> Self {
+        Self {
+            number,
+            user_name,
+            presence: None,
+            username: None,
+            nickname: None,
+            creator_name: None,
+            member_count: None,
+            now: None,
+            highest_role: None,
+            activity_priority: ActivityPriority::default(),
+            room: None,
+        }
+    }
+
+    /// Fills in everything that is known about the member, the creator channel, member count and
+    /// time are left to the caller
+    pub(crate) fn for_member(ctx: &Context, member: &'a Member, number: NonZeroU16) -> Self {
+        let mut context = TemplateContext::new(number, member.display_name());
+        context.presence = get_user_presence(ctx, &member.guild_id, &member.user.id);
+        context.username = Some(member.user.name.clone());
+        context.nickname = member.nick.clone();
+        context.highest_role = member
+            .guild_id
+            .to_guild_cached(ctx)
+            .and_then(|guild| guild.member_highest_role(member).map(|role| role.name.clone()));
+
+        context
+    }
+
+    /// The value of the placeholder, [`None`] when there is nothing to fill in
+    fn resolve(&self, placeholder: Placeholder) -> Option<String> {
+        match placeholder {
+            Placeholder::Number => Some(self.number.to_string()),
+            Placeholder::Name => Some(self.user_name.to_string()),
+            Placeholder::Room => Some(
+                self.room
+                    .clone()
+                    .unwrap_or_else(|| get_room(self.user_name, None, RoomLocale::English, &mut rng())),
+            ),
+            Placeholder::CurrentActivity => self
+                .get_activity()
+                .and_then(get_activity_name)
+                .map(|name| name.to_string()),
+            Placeholder::Username => self.username.clone(),
+            Placeholder::Nickname => self.nickname.clone(),
+            Placeholder::Creator => self.creator_name.clone(),
+            Placeholder::MemberCount => self.member_count.map(|member_count| member_count.to_string()),
+            Placeholder::Date => self.now.map(|now| now.format(DATE_FORMAT).to_string()),
+            Placeholder::Time => self.now.map(|now| now.format(TIME_FORMAT).to_string()),
+            Placeholder::HighestRole => self.highest_role.clone(),
+            Placeholder::ActivityDetails => self
+                .get_activity()
+                .and_then(|activity| activity.details.clone()),
+            Placeholder::ActivityType => self
+                .get_activity()
+                .and_then(|activity| ActivityKind::from_activity_type(activity.kind))
+                .map(|kind| kind.name().to_string()),
+        }
+    }
+
+    fn get_activity(&self) -> Option<&Activity> {
+        self.presence
+            .as_ref()
+            .and_then(|presence| self.activity_priority.choose(presence))
+    }
+}
+
+impl Template {
+    /// Parses a naming standard. `\` makes the next `\`, `%`, `{`, `}` or `|` plain text, a word
+    /// wrapped in `%` is a placeholder (optionally followed by `|filter`s) and any other `%` is plain text.
+    /// `{?` starts a conditional segment that ends at the next `}`
+    pub fn parse(source: &str) -> Result<Template, TemplateError> {
+        let mut rest = source;
+        let segments = parse_segments(&mut rest, false)?;
+
+        Ok(Template { segments })
+    }
+
+    /// Whether the placeholder is used anywhere, also inside conditional segments
+    pub(crate) fn uses(&self, placeholder: Placeholder) -> bool {
+        segments_use(&self.segments, placeholder)
+    }
+
+    /// Fills in the placeholders, the filled in values are used as they are and never parsed again
+    pub fn render(&self, context: &TemplateContext) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Expression(expression) => expression.render(context),
+                Segment::Conditional(segments) => render_conditional(segments, context).unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// Parses until the end of the source, or until the `}` that closes the conditional segment
+fn parse_segments(rest: &mut &str, in_conditional: bool) -> Result<Vec<Segment>, TemplateError> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+
+    loop {
+        let c = match rest.chars().next() {
+            None if in_conditional => return Err(TemplateError::UnclosedConditional),
+            None => break,
+            Some(c) => c,
+        };
+
+        match c {
+            '\\' => match rest[1..].chars().next() {
+                Some(escaped) if ESCAPABLE.contains(&escaped) => {
+                    text.push(escaped);
+                    *rest = &rest[1 + escaped.len_utf8()..];
+                }
+                _ => {
+                    text.push('\\');
+                    *rest = &rest[1..];
+                }
+            },
+            '%' => match find_expression_body(&rest[1..]) {
+                None => {
+                    text.push('%');
+                    *rest = &rest[1..];
+                }
+                Some(body) => {
+                    let expression = Expression::parse(body)?;
+
+                    push_text(&mut segments, &mut text);
+                    segments.push(Segment::Expression(expression));
+
+                    *rest = &rest[body.len() + 2..];
+                }
+            },
+            '{' if rest[1..].starts_with('?') => {
+                *rest = &rest[2..];
+                let conditional = parse_segments(rest, true)?;
+
+                push_text(&mut segments, &mut text);
+                segments.push(Segment::Conditional(conditional));
+            }
+            '}' if in_conditional => {
+                *rest = &rest[1..];
+                break;
+            }
+            _ => {
+                text.push(c);
+                *rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    push_text(&mut segments, &mut text);
+
+    Ok(segments)
+}
+
+fn segments_use(segments: &[Segment], placeholder: Placeholder) -> bool {
+    segments.iter().any(|segment| match segment {
+        Segment::Text(_) => false,
+        Segment::Expression(expression) => expression.placeholder == placeholder,
+        Segment::Conditional(segments) => segments_use(segments, placeholder),
+    })
+}
+
+fn push_text(segments: &mut Vec<Segment>, text: &mut String) {
+    if !text.is_empty() {
+        segments.push(Segment::Text(std::mem::take(text)));
+    }
+}
+
+/// Renders a conditional segment, [`None`] when any of its placeholders resolves to nothing
+fn render_conditional(segments: &[Segment], context: &TemplateContext) -> Option<String> {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => Some(text.clone()),
+            Segment::Expression(expression) => expression.evaluate(context).filter(|value| !value.is_empty()),
+            Segment::Conditional(segments) => Some(render_conditional(segments, context).unwrap_or_default()),
+        })
+        .collect()
+}
+
+/// Writes the template back as a naming standard, escaping text where needed
+impl Display for Template {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_segments(f, &self.segments)
+    }
+}
+
+fn write_segments(f: &mut Formatter<'_>, segments: &[Segment]) -> std::fmt::Result {
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => write!(f, "{}", escape(text, &TEXT_SPECIAL))?,
+            Segment::Expression(expression) => {
+                write!(f, "%{}", expression.placeholder.name())?;
+
+                for filter in &expression.filters {
+                    match filter {
+                        Filter::Upper => write!(f, "|upper")?,
+                        Filter::Lower => write!(f, "|lower")?,
+                        Filter::Truncate(length) => write!(f, "|truncate:{}", length)?,
+                        Filter::Default(default) => write!(f, "|default:{}", escape(default, &ARGUMENT_SPECIAL))?,
+                        Filter::Pad(width) => write!(f, "|pad:{}", width)?,
+                        Filter::Roman => write!(f, "|roman")?,
+                    }
+                }
+
+                write!(f, "%")?;
+            }
+            Segment::Conditional(segments) => {
+                write!(f, "{{?")?;
+                write_segments(f, segments)?;
+                write!(f, "}}")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Everything between the `%` signs of the placeholder at the start of `after_percent`, when it
+/// starts with a word followed by `%` or `|`. Any word counts, so `%Name%` or `%game1%` are
+/// reported as unknown instead of being kept as text
+fn find_expression_body(after_percent: &str) -> Option<&str> {
+    let body = &after_percent[..find_expression_end(after_percent)?];
+    let name = body.split('|').next().unwrap_or_default();
+
+    is_placeholder_name(name).then_some(body)
+}
+
+/// Where the `%` closing the placeholder is, escaped ones do not count
+fn find_expression_end(after_percent: &str) -> Option<usize> {
+    let mut chars = after_percent.char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '%' => return Some(index),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn is_placeholder_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Where the placeholder that is still being typed at the end of a naming standard starts. The naming
+/// standard is read the same way as [`Template::parse`] does, so an escaped `%` or a `%` that is
+/// only text never starts one
+pub(crate) fn find_unclosed_placeholder(source: &str) -> Option<usize> {
+    let mut rest = source;
+
+    while let Some(c) = rest.chars().next() {
+        let position = source.len() - rest.len();
+
+        match c {
+            '\\' => match rest[1..].chars().next() {
+                Some(escaped) if ESCAPABLE.contains(&escaped) => rest = &rest[1 + escaped.len_utf8()..],
+                _ => rest = &rest[1..],
+            },
+            '%' => match find_expression_body(&rest[1..]) {
+                Some(body) => rest = &rest[body.len() + 2..],
+                None => {
+                    let partial_body = &rest[1..];
+                    let name = partial_body.split('|').next().unwrap_or_default();
+
+                    if find_expression_end(partial_body).is_none() && (partial_body.is_empty() || is_placeholder_name(name)) {
+                        return Some(position);
+                    }
+                    rest = &rest[1..];
+                }
+            },
+            _ => rest = &rest[c.len_utf8()..],
+        }
+    }
+
+    None
+}
+
+/// Splits on every `separator` that is not escaped and removes the escapes
+fn split_unescaped(source: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = source.chars();
+
+    while let Some(c) = chars.next() {
+        let part = parts.last_mut().expect("There is always a part");
+
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if ESCAPABLE.contains(&escaped) => part.push(escaped),
+                Some(other) => {
+                    part.push('\\');
+                    part.push(other);
+                }
+                None => part.push('\\'),
+            },
+            c if c == separator => parts.push(String::new()),
+            c => part.push(c),
+        }
+    }
+
+    parts
+}
+
+fn escape(text: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        if special.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+fn to_roman(mut number: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"),
+        (100, "C"), (90, "XC"), (50, "L"), (40, "XL"),
+        (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+    ];
+
+    let mut roman = String::new();
+
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+
+    roman
+}
+
+/// One line per placeholder with what it is filled in with
+pub(crate) fn get_placeholder_help() -> String {
+    Placeholder::ALL
+        .iter()
+        .map(|placeholder| format!("`{}` {}", placeholder.token(), placeholder.description()))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub(crate) fn get_filter_help() -> String {
+    Filter::HELP
+        .iter()
+        .map(|(example, description)| format!("`|{}` {}", example, description))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Renders a naming standard, a naming standard that can not be parsed is used as the name as it is
+pub(crate) fn get_name_from_template(template_name: &str, context: &TemplateContext) -> String {
+    match Template::parse(template_name) {
+        Ok(template) => template.render(context),
+        Err(why) => {
+            println!("Could not parse naming standard `{}`: {:?}", template_name, why);
+            template_name.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::template::{
+        find_unclosed_placeholder, get_name_from_template, Expression, Filter, Placeholder, Segment, Template, TemplateContext,
+        TemplateError,
+    };
+    use chrono::TimeZone;
+    use proptest::prelude::*;
+    use serenity::all::Presence;
+    use std::num::NonZeroU16;
+
+    #[test]
+    fn check_template_name_1() {
+        let context = TemplateContext::new(NonZeroU16::new(83).unwrap(), "Inbound");
+        let name = get_name_from_template("%name% - %number%", &context);
+
+        assert_eq!(name, "Inbound - 83")
+    }
+
+    #[test]
+    fn check_template_name_2() {
+        let context = TemplateContext::new(NonZeroU16::new(42).unwrap(), "ⱤoᵀᴛᵥƝₓˣ");
+        let name = get_name_from_template("%name%'s %room%", &context);
+
+        let room = name.strip_prefix("ⱤoᵀᴛᵥƝₓˣ's "); // This was a user in a discord guild that did not have a normalised username
+        assert!(room.is_some()); // Assert that the prefix is "ⱤoᵀᴛᵥƝₓˣ's "
+
+        assert!(["Room", "Resort", "Retreat"].contains(&room.unwrap()));
+    }
+
+    #[test]
+    fn check_unknown_placeholders() {
+        let unknown = |name: &str| Err(TemplateError::UnknownPlaceholder(name.to_string()));
+
+        assert!(Template::parse("%name%'s %room%").is_ok());
+        assert!(Template::parse("100% %name%").is_ok());
+        assert!(Template::parse("Ranked %number%").is_ok());
+        assert_eq!(Template::parse("%name% - %game%"), unknown("game"));
+        assert_eq!(Template::parse("%Name%"), unknown("Name"));
+        assert_eq!(Template::parse("%ROOM% %name%"), unknown("ROOM"));
+        assert_eq!(Template::parse("%game1%"), unknown("game1"));
+        assert!(Template::parse("50%5% off").is_ok());
+        assert_eq!(Template::parse("%numbr% %number%"), unknown("numbr"));
+        assert!(Template::parse("\\%game\\%").is_ok());
+    }
+
+    #[test]
+    fn check_golden_templates() {
+        let golden = [
+            ("%name% - %number%", "Inbound - 7"),
+            ("Ranked %number%", "Ranked 7"),
+            ("100% %name%", "100% Inbound"),
+            ("%name% %current_activity%", "Inbound No Game"),
+            ("\\%number\\% %number%", "%number% 7"),
+            ("\\\\%number%", "\\7"),
+            ("¯\\_(ツ)_/¯", "¯\\_(ツ)_/¯"),
+            ("\\{%name%\\}", "{Inbound}"),
+            ("\\%Name\\% %%number%%", "%Name% %7%"),
+            ("", ""),
+        ];
+
+        let context = TemplateContext::new(NonZeroU16::new(7).unwrap(), "Inbound");
+
+        for (template_name, expected) in golden {
+            assert_eq!(get_name_from_template(template_name, &context), expected, "{}", template_name);
+        }
+    }
+
+    #[test]
+    fn check_values_are_not_expanded() {
+        let context = TemplateContext::new(NonZeroU16::new(3).unwrap(), "%number% \\% %room%");
+
+        assert_eq!(get_name_from_template("%name% - %number%", &context), "%number% \\% %room% - 3");
+    }
+
+    #[test]
+    fn check_template_segments() {
+        assert_eq!(
+            Template::parse("Squad %number%!").unwrap().segments,
+            vec![
+                Segment::Text("Squad ".to_string()),
+                Segment::Expression(Expression { placeholder: Placeholder::Number, filters: Vec::new() }),
+                Segment::Text("!".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            Template::parse("%name|truncate:12|upper%").unwrap().segments,
+            vec![Segment::Expression(Expression {
+                placeholder: Placeholder::Name,
+                filters: vec![Filter::Truncate(12), Filter::Upper],
+            })]
+        );
+    }
+
+    #[test]
+    fn check_filters() {
+        let golden = [
+            ("%name|upper%", "INBOUND"),
+            ("%name|lower%", "inbound"),
+            ("%name|truncate:3%", "Inb"),
+            ("%name|truncate:12%", "Inbound"),
+            ("Ranked %number|pad:2%", "Ranked 04"),
+            ("Squad %number|roman%", "Squad IV"),
+            ("%current_activity|default:Chilling%", "Chilling"),
+            ("%current_activity|upper%", "NO GAME"),
+            ("%current_activity|truncate:2%", "No"),
+            ("%current_activity|default:100\\% \\| fun%", "100% | fun"),
+            ("%name|truncate:3|upper%", "INB"),
+        ];
+
+        let context = TemplateContext::new(NonZeroU16::new(4).unwrap(), "Inbound");
+
+        for (template_name, expected) in golden {
+            assert_eq!(get_name_from_template(template_name, &context), expected, "{}", template_name);
+        }
+
+        let context = TemplateContext::new(NonZeroU16::new(1994).unwrap(), "Inbound");
+        assert_eq!(get_name_from_template("%number|roman%", &context), "MCMXCIV");
+    }
+
+    fn create_presence(activity_name: &str) -> Presence {
+        serde_json::from_value(serde_json::json!({
+            "user": { "id": "1" },
+            "status": "online",
+            "activities": [{ "name": activity_name, "type": 0, "created_at": 0 }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn check_conditional_segments() {
+        let template_name = "%name%{? - %current_activity%}";
+
+        let mut context = TemplateContext::new(NonZeroU16::new(1).unwrap(), "Inbound");
+        assert_eq!(get_name_from_template(template_name, &context), "Inbound");
+        assert_eq!(get_name_from_template("%name% - %current_activity%", &context), "Inbound - No Game");
+        assert_eq!(get_name_from_template("%name%{? - %current_activity|default:Chilling%}", &context), "Inbound - Chilling");
+        assert_eq!(get_name_from_template("{?Lobby}{? %current_activity%{? %number%}}", &context), "Lobby");
+
+        context.presence = Some(create_presence("Valorant"));
+        assert_eq!(get_name_from_template(template_name, &context), "Inbound - Valorant");
+        assert_eq!(get_name_from_template("{?%current_activity%{? #%number%}}", &context), "Valorant #1");
+
+        assert_eq!(Template::parse("%name%{? - %current_activity%"), Err(TemplateError::UnclosedConditional));
+
+        let template = Template::parse("%name%{? (%member_count|pad:2%)}").unwrap();
+        assert!(template.uses(Placeholder::MemberCount));
+        assert!(!template.uses(Placeholder::Number));
+        assert!(Template::parse("{%name%} {?%number%}}").is_ok());
+    }
+
+    #[test]
+    fn check_invalid_filters() {
+        let error = |template_name: &str| Template::parse(template_name).unwrap_err();
+
+        assert_eq!(error("%name|shout%"), TemplateError::UnknownFilter("shout".to_string()));
+        assert!(matches!(error("%name|truncate%"), TemplateError::InvalidFilterArgument(..)));
+        assert!(matches!(error("%name|truncate:0%"), TemplateError::InvalidFilterArgument(..)));
+        assert!(matches!(error("%number|pad:abc%"), TemplateError::InvalidFilterArgument(..)));
+        assert!(matches!(error("%name|upper:1%"), TemplateError::InvalidFilterArgument(..)));
+        assert_eq!(error("%name|roman%"), TemplateError::UnsupportedFilter("roman".to_string(), Placeholder::Name));
+        assert_eq!(error("%name|pad:2%"), TemplateError::UnsupportedFilter("pad".to_string(), Placeholder::Name));
+    }
+
+    #[test]
+    fn check_unclosed_placeholders() {
+        assert_eq!(find_unclosed_placeholder(""), None);
+        assert_eq!(find_unclosed_placeholder("Room"), None);
+        assert_eq!(find_unclosed_placeholder("%"), Some(0));
+        assert_eq!(find_unclosed_placeholder("%na"), Some(0));
+        assert_eq!(find_unclosed_placeholder("%name%"), None);
+        assert_eq!(find_unclosed_placeholder("%name% #%num"), Some(8));
+        assert_eq!(find_unclosed_placeholder("%name%%"), Some(6));
+        assert_eq!(find_unclosed_placeholder("100% %na"), Some(5));
+        assert_eq!(find_unclosed_placeholder("100% done"), None);
+        assert_eq!(find_unclosed_placeholder("50% off %"), Some(8));
+        assert_eq!(find_unclosed_placeholder("\\%na"), None);
+        assert_eq!(find_unclosed_placeholder("\\\\%na"), Some(2));
+        assert_eq!(find_unclosed_placeholder("Ωmega %"), Some(7));
+        assert_eq!(find_unclosed_placeholder("%name|upper% %number|pa"), Some(13));
+        assert_eq!(find_unclosed_placeholder("%current_activity|default:100\\% fun"), Some(0));
+        assert_eq!(find_unclosed_placeholder("%name|truncate:3%"), None);
+    }
+
+    #[test]
+    fn check_member_placeholders() {
+        let template_name = "%username% (%nickname%) in %creator% with %member_count%";
+
+        let mut context = TemplateContext::new(NonZeroU16::new(1).unwrap(), "Inbound");
+        assert_eq!(get_name_from_template(template_name, &context), " () in  with ");
+        assert_eq!(get_name_from_template("%name%{? aka %nickname%}", &context), "Inbound");
+
+        context.username = Some("inbound".to_string());
+        context.nickname = Some("Inbound".to_string());
+        context.creator_name = Some("Join to Create".to_string());
+        context.member_count = Some(3);
+        context.highest_role = Some("Moderator".to_string());
+        assert_eq!(get_name_from_template(template_name, &context), "inbound (Inbound) in Join to Create with 3");
+        assert_eq!(get_name_from_template("%highest_role|upper% %member_count|pad:2%", &context), "MODERATOR 03");
+
+        context.now = Some(chrono_tz::Europe::Amsterdam.with_ymd_and_hms(2024, 2, 29, 23, 5, 0).unwrap());
+        assert_eq!(get_name_from_template("%date% %time%", &context), "2024-02-29 23:05");
+
+        context.presence = Some(
+            serde_json::from_value(serde_json::json!({
+                "user": { "id": "1" },
+                "status": "online",
+                "activities": [{ "name": "Valorant", "type": 0, "created_at": 0, "details": "Competitive" }],
+            }))
+            .unwrap(),
+        );
+        assert_eq!(get_name_from_template("%current_activity%{? - %activity_details%}", &context), "Valorant - Competitive");
+        assert_eq!(get_name_from_template("%activity_type% %current_activity%", &context), "Playing Valorant");
+
+        context.presence = Some(create_presence("Valorant"));
+        assert_eq!(get_name_from_template("%current_activity%{? - %activity_details%}", &context), "Valorant");
+    }
+
+    proptest! {
+        #[test]
+        fn check_parse_never_panics(source in "\\PC*") {
+            let _ = Template::parse(&source);
+        }
+
+        #[test]
+        fn check_display_round_trip(source in "([a-z %\\\\{}|:_]|%name%|%number%|\\\\%|%name\\|default:[a-z%|\\\\]{0,3}%|%number\\|pad:2%|%name\\|upper%|\\{\\?|\\{\\?%name%\\}){0,40}") {
+            if let Ok(template) = Template::parse(&source) {
+                prop_assert_eq!(Template::parse(&template.to_string()), Ok(template));
+            }
+        }
+
+        #[test]
+        fn check_user_name_rendered_verbatim(user_name in "\\PC*") {
+            let context = TemplateContext::new(NonZeroU16::new(1).unwrap(), &user_name);
+
+            prop_assert_eq!(get_name_from_template("%name%", &context), user_name.clone());
+        }
+    }
+}
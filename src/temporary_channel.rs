@@ -1,10 +1,9 @@
 use std::num::NonZeroU16;
-use rand::{rng, Rng};
 use serde::{Deserialize, Serialize};
-use serenity::all::{ActivityType, ChannelId, Context, GuildId, Presence, UserId};
-use serenity::futures::AsyncReadExt;
-use serenity::model::prelude::*;
-use serenity::prelude::*;
+use serenity::all::{ChannelId, Context, GuildId, Presence, UserId};
+use unicode_normalization::char::{decompose_compatible, is_combining_mark};
+use unicode_script::{Script, UnicodeScript};
+use unicode_security::skeleton;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TemporaryVoiceChannel {
@@ -15,6 +14,21 @@ pub struct TemporaryVoiceChannel {
     pub(crate) name: String,
     pub(crate) template_name: String,
     pub(crate) number: NonZeroU16,
+    /// The number pool [`Self::number`] was taken from, freed there instead of on the creator channel
+    #[serde(default)]
+    pub(crate) number_pool: Option<String>,
+    /// The private text channel created alongside the voice channel
+    #[serde(default)]
+    pub(crate) text_channel_id: Option<ChannelId>,
+    /// Unix time the channel is deleted at when it stays empty
+    #[serde(default)]
+    pub(crate) pending_deletion_at: Option<u64>,
+    /// Bumped every time a deletion is scheduled, only the task holding the latest one deletes the channel
+    #[serde(default)]
+    pub(crate) pending_deletion_generation: u64,
+    /// The `%room%` picked when the channel was created, so renaming the channel keeps it
+    #[serde(default)]
+    pub(crate) room: Option<String>,
 }
 
 impl TemporaryVoiceChannel {
@@ -35,24 +49,15 @@ impl TemporaryVoiceChannel {
             name,
             template_name,
             number,
+            number_pool: None,
+            text_channel_id: None,
+            pending_deletion_at: None,
+            pending_deletion_generation: 0,
+            room: None,
         }
     }
 }
 
-pub(crate) fn get_name_from_template(
-    template_name: &String,
-    number: &NonZeroU16,
-    presence: Option<Presence>,
-    user_name: &str,
-) -> String {
-    let current_activity = get_presence_str(presence).unwrap_or_else(|| "No Game".to_string());
-
-    template_name
-        .replace("%number%", number.to_string().as_str())
-        .replace("%name%", user_name)
-        .replace("%room%", get_end_modifier(user_name))
-        .replace("%current_activity%", current_activity.as_str())
-}
 pub(crate) fn get_user_presence(
     ctx: &Context,
     guild_id: &GuildId,
@@ -60,133 +65,143 @@ pub(crate) fn get_user_presence(
 ) -> Option<Presence> {
     match guild_id.to_guild_cached(ctx) {
         None => None,
-        Some(guild_ref) => {
-            return match guild_ref.presences.get(user_id) {
-                None => None,
-                Some(presence) => Some(presence.to_owned()),
-            }
-        }
+        Some(guild_ref) => guild_ref.presences.get(user_id).cloned(),
     }
 }
 
-fn get_presence_str(presence: Option<Presence>) -> Option<String> {
-    match presence {
-        None => None,
-        Some(presence) => {
-            // TODO: Improve this so that it gets more types and order them
-            for activity in presence.activities {
-                if activity.kind == ActivityType::Playing {
-                    return Some(activity.name);
-                }
-            }
-
-            return None;
-        }
+/// The Latin letter a character from another script looks like, from the Unicode confusables
+/// (UTS #39). Lowercase letters like г only look Latin in some fonts while their uppercase form
+/// does not, those are kept as they are
+fn get_latin_lookalike(c: char) -> Option<char> {
+    let lower = c.to_lowercase().next()?;
+    let upper = lower.to_uppercase().next()?;
+
+    let lookalike = get_skeleton_letter(lower).filter(char::is_ascii_alphabetic)?;
+
+    match get_skeleton_letter(upper)?.script() {
+        Script::Latin => Some(lookalike),
+        _ => None,
     }
 }
 
-fn normalize_char(c: char) -> char {
-    match c {
-        'á' | 'à' | 'ä' | 'â' | 'ã' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
-        'Á' | 'À' | 'Ä' | 'Â' | 'Ã' | 'Å' | 'Ā' | 'Ă' | 'Ą' => 'A',
-        'ß' => 'b',
-        'ç' => 'c',
-        'Ç' => 'C',
-        'é' | 'è' | 'ë' | 'ê' | 'ę' | 'ė' | 'ē' => 'e',
-        'É' | 'È' | 'Ë' | 'Ê' | 'Ę' | 'Ė' | 'Ē' => 'E',
-        'í' | 'ì' | 'ï' | 'î' | 'į' | 'ī' | 'ᵢ' => 'i',
-        'Í' | 'Ì' | 'Ï' | 'Î' | 'Į' | 'Ī' => 'I',
-        'ñ' => 'n',
-        'Ñ' | 'Ɲ' => 'N',
-        'ó' | 'ò' | 'ö' | 'ô' | 'õ' | 'ø' | 'ō' | 'ő' => 'o',
-        'Ó' | 'Ò' | 'Ö' | 'Ô' | 'Õ' | 'Ø' | 'Ō' | 'Ő' => 'O',
-        'Ɽ' => 'R',
-        'ú' | 'ù' | 'ü' | 'û' | 'ū' | 'ů' | 'ű' => 'u',
-        'Ú' | 'Ù' | 'Ü' | 'Û' | 'Ū' | 'Ů' | 'Ű' => 'U',
-        'ÿ' | 'ý' => 'y',
-        'Ÿ' | 'Ý' => 'Y',
-        _ => c,
-    }
+/// The letter a small capital like ᴍ stands for, the confusables do not list these
+fn get_small_capital_letter(c: char) -> Option<char> {
+    let letter = match c {
+        'ᴀ' => 'a',
+        'ʙ' => 'b',
+        'ᴄ' => 'c',
+        'ᴅ' => 'd',
+        'ᴇ' => 'e',
+        'ꜰ' => 'f',
+        'ɢ' => 'g',
+        'ʜ' => 'h',
+        'ɪ' => 'i',
+        'ᴊ' => 'j',
+        'ᴋ' => 'k',
+        'ʟ' => 'l',
+        'ᴍ' => 'm',
+        'ɴ' => 'n',
+        'ᴏ' => 'o',
+        'ᴘ' => 'p',
+        'ꞯ' => 'q',
+        'ʀ' => 'r',
+        'ꜱ' => 's',
+        'ᴛ' => 't',
+        'ᴜ' => 'u',
+        'ᴠ' => 'v',
+        'ᴡ' => 'w',
+        'ʏ' => 'y',
+        'ᴢ' => 'z',
+        _ => return None,
+    };
+
+    Some(letter)
 }
 
-fn get_end_modifier(member_name: &str) -> &str {
-    let first_char_of_member_name = member_name.chars().next().unwrap_or_default();
+/// The skeleton of the character without accents, [`None`] when that is not a single letter
+fn get_skeleton_letter(c: char) -> Option<char> {
+    let mut letters = skeleton(c.encode_utf8(&mut [0; 4]))
+        .filter(|skeleton_char| !is_combining_mark(*skeleton_char))
+        .collect::<Vec<char>>()
+        .into_iter();
 
-    let end_modifiers = get_end_modifiers(first_char_of_member_name);
+    match (letters.next(), letters.next()) {
+        (Some(letter), None) if letter.is_alphabetic() => Some(letter),
+        _ => None,
+    }
+}
 
-    assert!(end_modifiers.len() > 0);
+/// The lowercase letter the character is or looks like, [`None`] when it is not a letter.
+/// Letters without a Latin lookalike are kept so word lists in other scripts can use them
+pub(crate) fn fold_char(c: char) -> Option<char> {
+    let mut base = None;
 
-    let len = end_modifiers.len();
-    let index = rng().random_range(0..len);
+    // Compatibility decomposition turns styled letters like 𝓘, Ｉ or ᵢ into I and splits off accents
+    decompose_compatible(c, |decomposed| {
+        if base.is_none() && !is_combining_mark(decomposed) {
+            base = Some(decomposed);
+        }
+    });
+
+    let base = base?;
+    let base = match base.is_ascii() {
+        true => base,
+        false => get_small_capital_letter(base)
+            .or_else(|| get_latin_lookalike(base))
+            .unwrap_or(base),
+    };
 
-    end_modifiers[index]
+    match base.is_alphabetic() {
+        true => base.to_lowercase().next(),
+        false => None,
+    }
 }
 
-fn get_end_modifiers(first_char_of_member_name: char) -> Vec<&'static str> {
-    let first_char_of_member_name = normalize_char(first_char_of_member_name);
-
-    return match first_char_of_member_name.to_ascii_lowercase() {
-        'a' => vec!["Atrium", "Arcade", "Arena", "Area"],
-        'b' => vec!["Bureau", "Base", "Building"],
-        'c' => vec!["Corner", "Court", "Cave", "City", "Cool-de-Sac", "Club", "Chill-Zone"],
-        'd' => vec!["Domain", "Den", "Depot", "District"],
-        'e' => vec!["Estate", "Embassy", "Entrance"],
-        'f' => vec!["Fortress", "Farmhouse", "Factory"],
-        'g' => vec!["Grounds", "Gallery", "Garden"],
-        'h' => vec!["Haven", "Hall", "Harbor"],
-        'i' => vec!["Institute", "Inn", "Island"],
-        'j' => vec!["Junction", "Jungle"],
-        'k' => vec!["Kingdom", "Keep", "Kitchen"],
-        'l' => vec!["Loft", "Library", "Lodge"],
-        'm' => vec!["Manor", "Museum", "Mill"],
-        'n' => vec!["Nook", "Nest", "Nave"],
-        'o' => vec!["Office", "Outpost", "Observatory"],
-        'p' => vec!["Plaza", "Palace", "Parlor"],
-        'q' => vec!["Quarters", "Quay", "Quadrangle"],
-        'r' => vec!["Room", "Resort", "Retreat"],
-        's' => vec!["Studio", "Sanctuary", "Store", "Sector", "Section"],
-        't' => vec!["Territory", "Tower", "Temple"],
-        'u' => vec!["University"],
-        'v' => vec!["Villa", "Valley", "Vault"],
-        'w' => vec!["Workshop", "Warehouse", "Wharf"],
-        'x' => vec!["Xystus"],
-        'y' => vec!["Yard", "Yacht", "Yardhouse"],
-        'z' => vec!["Zone"],
-        _ => vec!["VC"],
-    };
+/// The first letter of the name, skipping emoji, punctuation and anything else that is not a letter
+pub(crate) fn get_first_letter(member_name: &str) -> Option<char> {
+    member_name.chars().find_map(fold_char)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::temporary_channel::{get_end_modifiers, get_name_from_template};
-    use std::num::NonZeroU16;
+    use crate::temporary_channel::{fold_char, get_first_letter};
 
     #[test]
-    fn check_template_name_1() {
-        let template_name = "%name% - %number%";
-        let name = get_name_from_template(
-            &template_name.to_string(),
-            &NonZeroU16::new(83).unwrap(),
-            None,
-            "Inbound",
-        );
-
-        assert_eq!(name, "Inbound - 83")
+    fn check_first_letter() {
+        assert_eq!(get_first_letter("Inbound"), Some('i'));
+        assert_eq!(get_first_letter("𝓘𝓷𝓫𝓸𝓾𝓷𝓭"), Some('i'));
+        assert_eq!(get_first_letter("Ｉｎｂｏｕｎｄ"), Some('i'));
+        assert_eq!(get_first_letter("Ínbound"), Some('i'));
+        assert_eq!(get_first_letter("ⱤoᵀᴛᵥƝₓˣ"), Some('r'));
+        assert_eq!(get_first_letter("Дмитрий"), Some('д'));
+        assert_eq!(get_first_letter("Шоу"), Some('ш'));
+        assert_eq!(get_first_letter("Ярослав"), Some('я'));
+        assert_eq!(get_first_letter("Сергей"), Some('c'));
+        assert_eq!(get_first_letter("аnna"), Some('a'));
+        assert_eq!(get_first_letter("🎮 [TTV] ~Inbound"), Some('t'));
+        assert_eq!(get_first_letter("...!!! ᴍᴏᴏɴ"), Some('m'));
+        assert_eq!(get_first_letter("ʀᴏʙɪɴ"), Some('r'));
+        assert_eq!(get_first_letter("123 🎮"), None);
+        assert_eq!(get_first_letter(""), None);
     }
 
     #[test]
-    fn check_template_name_2() {
-        let template_name = "%name%'s %room%";
-        let name = get_name_from_template(
-            &template_name.to_string(),
-            &NonZeroU16::new(42).unwrap(),
-            None,
-            "ⱤoᵀᴛᵥƝₓˣ",
-        );
-
-        let room = name.strip_prefix("ⱤoᵀᴛᵥƝₓˣ's "); // This was a user in a discord guild that did not have a normalised username
-        assert_eq!(room.is_some(), true); // Assert that the prefix is "ⱤoᵀᴛᵥƝₓˣ's "
-
-        assert!(get_end_modifiers('Ɽ').contains(&room.unwrap()));
+    fn check_confusable_letters() {
+        // Cyrillic letters that are confusable with Latin ones, and ones that only look alike in some fonts
+        assert_eq!(fold_char('а'), Some('a'));
+        assert_eq!(fold_char('Р'), Some('p'));
+        assert_eq!(fold_char('х'), Some('x'));
+        for cyrillic in ['п', 'г', 'Г', 'ц', 'ш', 'Ш', 'ь', 'Ь'] {
+            assert_eq!(fold_char(cyrillic), cyrillic.to_lowercase().next(), "{}", cyrillic);
+        }
+        assert_eq!(fold_char('ß'), Some('ß'));
+
+        // Fullwidth
+        assert_eq!(fold_char('Ｗ'), Some('w'));
+        assert_eq!(fold_char('ｒ'), Some('r'));
+
+        // Mathematical script
+        assert_eq!(fold_char('𝒲'), Some('w'));
+        assert_eq!(fold_char('𝓇'), Some('r'));
     }
 }